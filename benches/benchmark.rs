@@ -1,15 +1,36 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
 
-fn fibonacci(n: u64) -> u64 {
-    match n {
-        0 => 1,
-        1 => 1,
-        n => fibonacci(n - 1) + fibonacci(n - 2),
-    }
-}
+use aoc2021::{assets, solvers};
+use criterion::{criterion_group, criterion_main, Criterion};
 
+/// Benchmarks parsing and the full solve of each part against the day's
+/// real input. Days whose input is not checked out are skipped.
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("fib 20", |b| b.iter(|| fibonacci(black_box(20))));
+    let assets = assets::default_dir();
+    let mut days: Vec<_> = solvers().iter().map(|s| s.day()).collect();
+    days.dedup();
+
+    for day in days {
+        let path = assets.join(format!("day{}.0.txt", day));
+        let input = match std::fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(_) => {
+                eprintln!("skipping day{}: {} not found", day, path.display());
+                continue;
+            }
+        };
+
+        let mut group = c.benchmark_group(format!("day{}", day));
+        for s in solvers().iter().filter(|s| s.day() == day) {
+            group.bench_function(format!("part{}/parse", s.part()), |b| {
+                b.iter(|| s.parse(black_box(&input)).unwrap())
+            });
+            group.bench_function(format!("part{}/solve", s.part()), |b| {
+                b.iter(|| s.solve(black_box(&input)).unwrap())
+            });
+        }
+        group.finish();
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
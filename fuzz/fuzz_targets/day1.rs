@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc2021::{day1, solvers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day1::parse_input::<i64>(input);
+    let _ = day1::parse_input::<f64>(input);
+    for s in solvers().iter().filter(|s| s.day() == 1) {
+        let _ = s.solve(input);
+    }
+    let _ = day1::Part1::solve_reader(input.as_bytes());
+    let _ = day1::Part2::solve_reader(input.as_bytes());
+});
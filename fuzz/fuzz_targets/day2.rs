@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc2021::{day2, solvers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day2::parse_input(input);
+    for s in solvers().iter().filter(|s| s.day() == 2) {
+        let _ = s.solve(input);
+    }
+    let _ = day2::Part1::solve_reader(input.as_bytes());
+    let _ = day2::Part2::solve_reader(input.as_bytes());
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc2021::{day3, solvers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day3::parse_input(input);
+    for s in solvers().iter().filter(|s| s.day() == 3) {
+        let _ = s.solve(input);
+    }
+    let _ = day3::Part1::solve_reader(input.as_bytes());
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc2021::{day4, solvers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day4::parse_input(input);
+    for s in solvers().iter().filter(|s| s.day() == 4) {
+        let _ = s.solve(input);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc2021::{day5, solvers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day5::parse_input(input);
+    for s in solvers().iter().filter(|s| s.day() == 5) {
+        let _ = s.solve(input);
+    }
+});
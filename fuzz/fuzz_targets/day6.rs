@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc2021::{day6, solvers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day6::parse_input(input);
+    for s in solvers().iter().filter(|s| s.day() == 6) {
+        let _ = s.solve(input);
+    }
+    let _ = day6::Part1::solve_reader(input.as_bytes());
+    let _ = day6::Part2::solve_reader(input.as_bytes());
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc2021::{day8, solvers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day8::parse_input(input);
+    for s in solvers().iter().filter(|s| s.day() == 8) {
+        let _ = s.solve(input);
+    }
+});
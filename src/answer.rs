@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    /// An integer too large for [`Answer::Int`].
+    Big(i128),
+    /// An answer that is read rather than computed, e.g. letters rendered on
+    /// a display.
+    Text(String),
+}
+
+macro_rules! from_small {
+    ($($t:ty)+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    Answer::Int(v.into())
+                }
+            }
+        )+
+    };
+}
+from_small!(i8 u8 i16 u16 i32 u32 i64);
+
+macro_rules! from_large {
+    ($($t:ty)+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    match i64::try_from(v) {
+                        Ok(v) => Answer::Int(v),
+                        Err(_) => Answer::Big(v as i128),
+                    }
+                }
+            }
+        )+
+    };
+}
+from_large!(u64 usize i128);
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(v: &str) -> Self {
+        Answer::Text(v.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::Big(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+#[test]
+fn test_from() {
+    assert_eq!(Answer::from(7u32), Answer::Int(7));
+    assert_eq!(Answer::from(26984457539usize), Answer::Int(26984457539));
+    assert_eq!(Answer::from(u64::MAX), Answer::Big(u64::MAX as i128));
+    assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+    assert_eq!(Answer::from("EFLFJGRF"), Answer::Text("EFLFJGRF".into()));
+}
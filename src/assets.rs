@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// An input file named after the `dayN.K.txt` convention, where `K` numbers
+/// the variants of a day's input. Examples are named `dayN.K.test.txt`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InputFile {
+    pub day: u8,
+    pub example: bool,
+    pub variant: u32,
+    pub path: PathBuf,
+}
+
+impl InputFile {
+    /// `None` if the file name does not follow the convention.
+    pub fn from_path(path: &Path) -> Option<InputFile> {
+        let name = path.file_name()?.to_str()?;
+        let stem = name.strip_prefix("day")?.strip_suffix(".txt")?;
+        let (stem, example) = match stem.strip_suffix(".test") {
+            Some(stem) => (stem, true),
+            None => (stem, false),
+        };
+        let (day, variant) = stem.split_once('.')?;
+        Some(InputFile {
+            day: day.parse().ok()?,
+            example,
+            variant: variant.parse().ok()?,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// The file holding the expected answers for this input, which need not
+    /// exist. See [`Manifest`](crate::manifest::Manifest).
+    pub fn sidecar(&self) -> PathBuf {
+        self.path.with_extension("answers")
+    }
+
+    /// The file name, which identifies the input in reports and manifests.
+    pub fn name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+    }
+}
+
+/// The `assets` directory next to the crate manifest.
+///
+/// This is a checkout of a private repository holding the real puzzle
+/// inputs, so it may be missing.
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+}
+
+/// The `samples` directory next to the crate manifest, holding the example
+/// inputs from the puzzle descriptions.
+pub fn samples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("samples")
+}
+
+/// Every input file in `dir`, ordered by day, real inputs before examples,
+/// then by variant.
+pub fn discover(dir: &Path) -> Result<Vec<InputFile>> {
+    let mut out = Vec::new();
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("could not read {}", dir.display()))?;
+    for entry in entries {
+        if let Some(input) = InputFile::from_path(&entry?.path()) {
+            out.push(input);
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+#[test]
+fn test_input_file() {
+    let f = InputFile::from_path(Path::new("assets/day12.3.test.txt")).unwrap();
+    assert_eq!((f.day, f.variant, f.example), (12, 3, true));
+    assert_eq!(f.name(), "day12.3.test.txt");
+    assert_eq!(f.sidecar(), Path::new("assets/day12.3.test.answers"));
+    let f = InputFile::from_path(Path::new("day1.0.txt")).unwrap();
+    assert_eq!((f.day, f.variant, f.example), (1, 0, false));
+    assert!(InputFile::from_path(Path::new("day1.txt")).is_none());
+    assert!(InputFile::from_path(Path::new("answers.txt")).is_none());
+    assert!(InputFile::from_path(Path::new("day1.0.md")).is_none());
+}
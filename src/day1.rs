@@ -1,47 +1,302 @@
-use anyhow::Result;
-use nom::{
-    character::complete::{digit1, line_ending},
-    combinator::{map_res, opt},
-    multi::many1,
-    sequence::terminated,
-    IResult,
+use std::{collections::VecDeque, fmt, io::BufRead};
+
+use anyhow::{bail, Context, Result};
+use nom::IResult;
+
+use crate::{
+    error::{parse_line, ParseError},
+    parsers::{decimal, lines, normalize, records, signed, unsigned},
+    solver::Puzzle,
 };
 
 pub struct Part1;
 pub struct Part2;
 
-fn parse(input: &str) -> IResult<&str, Vec<i64>> {
-    many1(terminated(
-        map_res(digit1, |e| i64::from_str_radix(e, 10)),
-        opt(line_ending),
-    ))(input)
+/// A depth measurement: an integer, which may be negative, or a decimal
+/// number.
+pub trait Depth: Copy + PartialOrd {
+    /// A type to take differences and sums of measurements in, wide enough
+    /// that they only overflow for `i128` measurements.
+    type Wide: Wide;
+
+    /// Parse a single measurement, e.g. `-12` or `3.5`.
+    fn parse(input: &str) -> IResult<&str, Self>;
+
+    fn widen(self) -> Self::Wide;
+}
+
+/// The arithmetic [`analyze`] does on [`Depth::Wide`] measurements.
+pub trait Wide: Copy + fmt::Debug + fmt::Display {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    /// Whether `self` is further from zero than `other`.
+    fn larger(self, other: Self) -> bool;
+}
+
+impl Wide for i128 {
+    fn checked_add(self, other: i128) -> Option<i128> {
+        i128::checked_add(self, other)
+    }
+
+    fn checked_sub(self, other: i128) -> Option<i128> {
+        i128::checked_sub(self, other)
+    }
+
+    fn larger(self, other: i128) -> bool {
+        self.unsigned_abs() > other.unsigned_abs()
+    }
+}
+
+impl Wide for f64 {
+    fn checked_add(self, other: f64) -> Option<f64> {
+        Some(self + other)
+    }
+
+    fn checked_sub(self, other: f64) -> Option<f64> {
+        Some(self - other)
+    }
+
+    fn larger(self, other: f64) -> bool {
+        self.abs() > other.abs()
+    }
+}
+
+macro_rules! depth {
+    ($parser:ident, $wide:ty: $($t:ty)+) => {
+        $(
+            impl Depth for $t {
+                type Wide = $wide;
+
+                fn parse(input: &str) -> IResult<&str, Self> {
+                    $parser(input)
+                }
+
+                fn widen(self) -> $wide {
+                    self as $wide
+                }
+            }
+        )+
+    };
+}
+depth!(unsigned, i128: u8 u16 u32 u64 usize);
+depth!(signed, i128: i8 i16 i32 i64 i128 isize);
+depth!(decimal, f64: f32 f64);
+
+/// One depth measurement per line.
+pub fn parse<T: Depth>(input: &str) -> IResult<&str, Vec<T>> {
+    lines(T::parse)(input)
+}
+
+/// Parse a whole puzzle input, failing with a [`ParseError`] that gives the
+/// line number of the first blank or malformed line.
+pub fn parse_input<T: Depth>(input: &str) -> Result<Vec<T>> {
+    let input = normalize(input);
+    if input.is_empty() {
+        return Err(error(1, "", "expected a number").into());
+    }
+    let mut xs = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            return Err(error(i + 1, line, "blank line").into());
+        }
+        xs.push(parse_line(T::parse, line, i + 1)?);
+    }
+    Ok(xs)
+}
+
+fn error(line: usize, snippet: &str, message: &str) -> ParseError {
+    ParseError {
+        line,
+        column: 1,
+        snippet: snippet.into(),
+        message: message.into(),
+    }
+}
+
+/// How many sums of `k` consecutive measurements are larger than the sum
+/// before, or no smaller if not `strict`. `k` must be at least 1.
+///
+/// Neighbouring sums share all but their first and last measurements, so
+/// only those are compared.
+pub fn count_window_increases<T: PartialOrd>(xs: &[T], k: usize, strict: bool) -> Result<usize> {
+    if k == 0 {
+        bail!("the window must hold at least one measurement");
+    }
+    if k >= xs.len() {
+        // at most one full window, so nothing to compare
+        return Ok(0);
+    }
+    Ok(xs
+        .windows(k + 1)
+        .filter(|w| {
+            let (first, last) = (&w[0], &w[k]);
+            if strict {
+                last > first
+            } else {
+                last >= first
+            }
+        })
+        .count())
+}
+
+/// What a depth series looks like, beyond how often it increases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis<W = i128> {
+    pub measurements: usize,
+    /// The start and length of the first of the longest runs of strictly
+    /// increasing measurements.
+    pub longest_run: Option<(usize, usize)>,
+    /// The index of the first of the largest changes between neighbouring
+    /// measurements, by size, and the change, which may be negative.
+    pub largest_jump: Option<(usize, W)>,
+    /// The index of every measurement larger than the one before.
+    pub increases: Vec<usize>,
+    /// The sums of each `window` consecutive measurements. Like the jumps,
+    /// they are [`Depth::Wide`].
+    pub smoothed: Vec<W>,
+    pub window: usize,
+}
+
+/// Analyze `xs`, smoothing it over windows of `window` measurements.
+///
+/// Fails if a jump or a sum doesn't fit in [`Depth::Wide`], which only
+/// happens for `i128` measurements.
+pub fn analyze<T: Depth>(xs: &[T], window: usize) -> Result<Analysis<T::Wide>> {
+    if window == 0 {
+        bail!("the window must hold at least one measurement");
+    }
+    let mut longest_run = None;
+    let mut start = 0;
+    for i in 0..xs.len() {
+        if i > 0 && xs[i] <= xs[i - 1] {
+            start = i;
+        }
+        let len = i + 1 - start;
+        if longest_run.is_none_or(|(_, longest)| len > longest) {
+            longest_run = Some((start, len));
+        }
+    }
+    let mut largest_jump: Option<(usize, T::Wide)> = None;
+    for (i, w) in xs.windows(2).enumerate() {
+        let jump = w[1]
+            .widen()
+            .checked_sub(w[0].widen())
+            .with_context(|| format!("the jump at index {} is out of range", i + 1))?;
+        if largest_jump.is_none_or(|(_, largest)| jump.larger(largest)) {
+            largest_jump = Some((i + 1, jump));
+        }
+    }
+    let smoothed = xs
+        .windows(window)
+        .enumerate()
+        .map(|(i, w)| {
+            w[1..]
+                .iter()
+                .try_fold(w[0].widen(), |sum, x| sum.checked_add(x.widen()))
+                .with_context(|| format!("the sum from index {} is out of range", i))
+        })
+        .collect::<Result<_>>()?;
+    Ok(Analysis {
+        measurements: xs.len(),
+        longest_run,
+        largest_jump,
+        increases: (1..xs.len()).filter(|&i| xs[i] > xs[i - 1]).collect(),
+        smoothed,
+        window,
+    })
+}
+
+impl<W: fmt::Display> fmt::Display for Analysis<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list<T: ToString>(xs: &[T]) -> String {
+            xs.iter().map(T::to_string).collect::<Vec<_>>().join(" ")
+        }
+        writeln!(f, "measurements: {}", self.measurements)?;
+        if let Some((start, len)) = self.longest_run {
+            writeln!(f, "longest increasing run: {} from index {}", len, start)?;
+        }
+        if let Some((i, jump)) = &self.largest_jump {
+            writeln!(f, "largest jump: {:+} at index {}", jump, i)?;
+        }
+        writeln!(
+            f,
+            "increases: {} at {}",
+            self.increases.len(),
+            list(&self.increases)
+        )?;
+        write!(f, "smoothed over {}: {}", self.window, list(&self.smoothed))
+    }
+}
+
+/// How many measurements are larger than the one `k` places before, keeping
+/// only the last `k` in memory.
+fn count_increases<T: PartialOrd>(xs: impl Iterator<Item = Result<T>>, k: usize) -> Result<i64> {
+    let mut window = VecDeque::with_capacity(k + 1);
+    let mut out = 0;
+    for x in xs {
+        let x = x?;
+        if window.len() == k {
+            if let Some(first) = window.pop_front() {
+                out += (x > first) as i64;
+            }
+        }
+        window.push_back(x);
+    }
+    Ok(out)
 }
 
 impl Part1 {
+    /// Solve while reading the measurements one at a time, in constant memory.
+    pub fn solve_reader(reader: impl BufRead) -> Result<i64> {
+        count_increases(records(reader, signed::<i64>), 1)
+    }
+}
+
+impl Part2 {
+    /// Solve while reading the measurements one at a time, in constant memory.
+    pub fn solve_reader(reader: impl BufRead) -> Result<i64> {
+        // sums of windows of 3 differ by their first and last measurements
+        count_increases(records(reader, signed::<i64>), 3)
+    }
+}
+
+impl Puzzle for Part1 {
+    const DAY: u8 = 1;
+    const PART: u8 = 1;
+    const NAME: &'static str = "Sonar Sweep";
+
+    type Input = Vec<i64>;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        parse_input(input)
+    }
+
     /// Input is a list of measurements.
     /// How many measurements are larger than the previous measurement?
-    pub fn solve(input: &str) -> Result<i64> {
-        let (rest, xs) = parse(input).unwrap();
-        assert!(rest.len() == 0);
+    fn solve_parsed(xs: &Vec<i64>) -> Result<i64> {
+        Ok(count_window_increases(xs, 1, true)? as i64)
+    }
+}
 
-        let out = xs.windows(2).map(|w| (w[1] > w[0]) as i64).sum();
+impl Puzzle for Part2 {
+    const DAY: u8 = 1;
+    const PART: u8 = 2;
+    const NAME: &'static str = "Sonar Sweep";
 
-        Ok(out)
+    type Input = Vec<i64>;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        parse_input(input)
     }
-}
 
-impl Part2 {
     /// Input is a list of measurements.
     /// Over sliding windows of size 3, how many are larger than the previous measurement?
-    pub fn solve(input: &str) -> Result<i64> {
-        let (rest, xs) = parse(input).unwrap();
-        assert!(rest.len() == 0);
-
+    fn solve_parsed(xs: &Vec<i64>) -> Result<i64> {
         // 012
         //  123
-        let out = xs.windows(4).map(|w| (w[3] > w[0]) as i64).sum();
-
-        Ok(out)
+        Ok(count_window_increases(xs, 3, true)? as i64)
     }
 }
 
@@ -49,10 +304,119 @@ impl Part2 {
 fn day1() {
     assert_eq!(
         7,
-        Part1::solve(include_str!("../assets/day1.0.test.txt")).unwrap()
+        Part1::solve(include_str!("../samples/day1.0.test.txt")).unwrap()
     );
     assert_eq!(
         5,
-        Part2::solve(include_str!("../assets/day1.0.test.txt")).unwrap()
+        Part2::solve(include_str!("../samples/day1.0.test.txt")).unwrap()
+    );
+}
+
+#[test]
+fn test_solve_reader() {
+    let input = include_str!("../samples/day1.0.test.txt");
+    assert_eq!(Part1::solve_reader(input.as_bytes()).unwrap(), 7);
+    assert_eq!(Part2::solve_reader(input.as_bytes()).unwrap(), 5);
+    assert!(Part1::solve_reader("1\nx\n".as_bytes()).is_err());
+    assert!(Part1::solve_reader("".as_bytes()).is_err());
+    assert!(Part2::solve_reader("".as_bytes()).is_err());
+}
+
+#[test]
+fn test_count_window_increases() {
+    let xs = parse_input::<i64>(include_str!("../samples/day1.0.test.txt")).unwrap();
+    assert_eq!(count_window_increases(&xs, 1, true).unwrap(), 7);
+    assert_eq!(count_window_increases(&xs, 3, true).unwrap(), 5);
+    assert_eq!(count_window_increases(&xs, 1, false).unwrap(), 7);
+    assert_eq!(count_window_increases(&xs, 10, true).unwrap(), 0);
+
+    let flat = [1, 1, 2, 2, 1];
+    assert_eq!(count_window_increases(&flat, 1, true).unwrap(), 1);
+    assert_eq!(count_window_increases(&flat, 1, false).unwrap(), 3);
+    assert_eq!(count_window_increases(&flat, 2, false).unwrap(), 2);
+    assert!(count_window_increases(&flat, 0, true).is_err());
+    assert_eq!(count_window_increases(&flat, flat.len(), true).unwrap(), 0);
+    assert_eq!(count_window_increases(&flat, usize::MAX, true).unwrap(), 0);
+}
+
+#[test]
+fn test_analyze() {
+    let xs = parse_input::<i64>(include_str!("../samples/day1.0.test.txt")).unwrap();
+    let analysis = analyze(&xs, 3).unwrap();
+    assert_eq!(analysis.measurements, 10);
+    assert_eq!(analysis.longest_run, Some((0, 4)));
+    assert_eq!(analysis.largest_jump, Some((6, 33)));
+    assert_eq!(analysis.increases, vec![1, 2, 3, 5, 6, 7, 9]);
+    assert_eq!(analysis.increases.len(), 7);
+    assert_eq!(
+        analysis.smoothed,
+        vec![607, 618, 618, 617, 647, 716, 769, 792]
+    );
+    assert_eq!(
+        analysis.to_string(),
+        "measurements: 10
+longest increasing run: 4 from index 0
+largest jump: +33 at index 6
+increases: 7 at 1 2 3 5 6 7 9
+smoothed over 3: 607 618 618 617 647 716 769 792"
+    );
+
+    assert_eq!(
+        analyze(&[5i64, 1, 2], 1).unwrap().largest_jump,
+        Some((1, -4))
+    );
+    let extreme = analyze(&[i64::MIN, i64::MAX, i64::MAX], 2).unwrap();
+    assert_eq!(extreme.largest_jump, Some((1, u64::MAX as i128)));
+    assert_eq!(extreme.smoothed, vec![-1, 2 * i64::MAX as i128]);
+    let empty = analyze::<i64>(&[], 3).unwrap();
+    assert_eq!((empty.longest_run, empty.largest_jump), (None, None));
+    assert!(empty.smoothed.is_empty());
+    assert!(analyze(&xs, 0).is_err());
+
+    let decimals = analyze(&[1.5f32, 0.25, 2.0], 2).unwrap();
+    assert_eq!(decimals.largest_jump, Some((2, 1.75)));
+    assert_eq!(decimals.smoothed, vec![1.75, 2.25]);
+    assert_eq!(analyze(&[3u8, 1], 1).unwrap().largest_jump, Some((1, -2)));
+    let huge = parse_input::<i128>("99999999999999999999\n1\n").unwrap();
+    assert_eq!(
+        analyze(&huge, 2).unwrap().smoothed,
+        vec![100000000000000000000]
+    );
+    assert!(analyze(&[i128::MAX, i128::MIN], 1).is_err());
+    assert!(analyze(&[i128::MAX, 0], 1).is_ok());
+    assert!(analyze(&[i128::MAX, 1], 2).is_err());
+}
+
+#[test]
+fn test_signed_and_decimal() {
+    assert_eq!(parse_input::<i64>("-3\n+2\n0\n").unwrap(), vec![-3, 2, 0]);
+    assert_eq!(Part1::solve("-3\n-2\n-5\n").unwrap(), 1);
+    assert_eq!(Part1::solve_reader("-3\n-2\n-5\n".as_bytes()).unwrap(), 1);
+
+    let xs = parse_input::<f64>("1.5\n1.25\n-2\n1.75\n").unwrap();
+    assert_eq!(xs, vec![1.5, 1.25, -2.0, 1.75]);
+    assert_eq!(count_window_increases(&xs, 1, true).unwrap(), 1);
+    assert_eq!(count_window_increases(&xs, 3, true).unwrap(), 1);
+
+    let error = |input: &str| {
+        let e = parse_input::<i64>(input)
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        (e.line, e.column, e.message)
+    };
+    assert_eq!(error("1\n\n2\n"), (2, 1, "blank line".into()));
+    assert_eq!(error("1\n  \n2\n"), (2, 1, "blank line".into()));
+    let e = Part1::solve_reader("1\n\n2\n".as_bytes())
+        .unwrap_err()
+        .downcast::<ParseError>()
+        .unwrap();
+    assert_eq!((e.line, e.column, e.message), (2, 1, "blank line".into()));
+    assert_eq!(error("1\n2\n3.5\n"), (3, 2, "unexpected input".into()));
+    assert_eq!(error("1\nabc\n"), (2, 1, "expected a number".into()));
+    assert_eq!(error(""), (1, 1, "expected a number".into()));
+    assert_eq!(
+        parse_input::<u32>("1\n-2\n").unwrap_err().to_string(),
+        "line 2, column 1: expected a number\n    -2\n    ^"
     );
 }
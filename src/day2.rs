@@ -1,134 +1,386 @@
-use anyhow::Result;
+use std::io::BufRead;
+
+use anyhow::{anyhow, Result};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{digit1, line_ending, multispace1},
-    combinator::{map, map_res, opt, value},
-    multi::many1,
-    sequence::{separated_pair, terminated},
+    character::complete::space1,
+    combinator::{map, value},
+    sequence::separated_pair,
     IResult,
 };
 
+use crate::{
+    error::parse_all,
+    parsers::{lines, records, unsigned},
+    solver::Puzzle,
+};
+
 pub struct Part1;
 pub struct Part2;
 
-#[derive(Clone, Copy, Debug)]
-enum Dir {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dir {
     F,
     U,
     D,
 }
 
-#[derive(Debug)]
-struct Move {
-    dir: Dir,
-    amt: i64,
+/// A submarine command, e.g. `forward 5`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub dir: Dir,
+    pub amt: i64,
 }
 
-#[derive(Debug, Default)]
-struct State {
-    depth: i64,
-    x: i64,
-}
-
-impl State {
-    fn update(self, m: &Move) -> State {
-        let State { depth, x } = self;
-        let (x, depth) = match m {
-            Move { dir: Dir::U, amt } => (x, depth - amt),
-            Move { dir: Dir::D, amt } => (x, depth + amt),
-            Move { dir: Dir::F, amt } => (self.x + amt, depth),
-        };
-        State { depth, x }
+/// Where the submarine is and, for models that steer, where it is aiming.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    pub x: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl Position {
+    /// The horizontal position times the depth.
+    pub fn product(&self) -> Result<i64> {
+        self.x.checked_mul(self.depth).ok_or_else(out_of_range)
+    }
+}
+
+fn out_of_range() -> anyhow::Error {
+    anyhow!("the submarine went out of range")
+}
+
+/// A way to interpret [`Move`]s.
+pub trait SubmarineModel: Sync {
+    /// What the model is called on the command line.
+    fn name(&self) -> &'static str;
+
+    /// Where `m` takes the submarine from `p`, or `None` if that no longer
+    /// fits.
+    fn step(&self, p: Position, m: &Move) -> Option<Position>;
+}
+
+/// Part 1: up and down change the depth directly.
+pub struct Direct;
+
+/// Part 2: up and down change the aim, and moving forward also dives by the
+/// aim times the distance.
+pub struct Aimed;
+
+impl SubmarineModel for Direct {
+    fn name(&self) -> &'static str {
+        "direct"
+    }
+
+    fn step(&self, p: Position, m: &Move) -> Option<Position> {
+        Some(match m.dir {
+            Dir::U => Position {
+                depth: p.depth.checked_sub(m.amt)?,
+                ..p
+            },
+            Dir::D => Position {
+                depth: p.depth.checked_add(m.amt)?,
+                ..p
+            },
+            Dir::F => Position {
+                x: p.x.checked_add(m.amt)?,
+                ..p
+            },
+        })
+    }
+}
+
+impl SubmarineModel for Aimed {
+    fn name(&self) -> &'static str {
+        "aimed"
+    }
+
+    fn step(&self, p: Position, m: &Move) -> Option<Position> {
+        Some(match m.dir {
+            Dir::U => Position {
+                aim: p.aim.checked_sub(m.amt)?,
+                ..p
+            },
+            Dir::D => Position {
+                aim: p.aim.checked_add(m.amt)?,
+                ..p
+            },
+            Dir::F => Position {
+                x: p.x.checked_add(m.amt)?,
+                depth: p.depth.checked_add(p.aim.checked_mul(m.amt)?)?,
+                ..p
+            },
+        })
+    }
+}
+
+static MODELS: &[&dyn SubmarineModel] = &[&Direct, &Aimed];
+
+/// Every model, in the order of the puzzle parts.
+pub fn models() -> &'static [&'static dyn SubmarineModel] {
+    MODELS
+}
+
+/// The model called `name`.
+pub fn model(name: &str) -> Result<&'static dyn SubmarineModel> {
+    MODELS
+        .iter()
+        .copied()
+        .find(|m| m.name() == name)
+        .ok_or_else(|| {
+            let names: Vec<_> = MODELS.iter().map(|m| m.name()).collect();
+            anyhow!("unknown model {:?}, expected {}", name, names.join(" or "))
+        })
+}
+
+/// Where `model` takes the submarine after all of `moves`.
+pub fn dive<M>(model: &M, moves: impl IntoIterator<Item = Move>) -> Result<Position>
+where
+    M: SubmarineModel + ?Sized,
+{
+    moves.into_iter().try_fold(Position::default(), |p, m| {
+        model.step(p, &m).ok_or_else(out_of_range)
+    })
+}
+
+/// The [`Position`] after each of `moves`, as `model` interprets them.
+///
+/// Yields an error and stops if the submarine goes out of range.
+pub fn trace<M, I>(model: &M, moves: I) -> Trace<'_, M, I::IntoIter>
+where
+    M: SubmarineModel + ?Sized,
+    I: IntoIterator<Item = Move>,
+{
+    Trace {
+        model,
+        moves: moves.into_iter(),
+        position: Some(Position::default()),
+    }
+}
+
+/// The iterator returned by [`trace`].
+pub struct Trace<'m, M: ?Sized, I> {
+    model: &'m M,
+    moves: I,
+    /// `None` once the submarine went out of range.
+    position: Option<Position>,
+}
+
+impl<M, I> Iterator for Trace<'_, M, I>
+where
+    M: SubmarineModel + ?Sized,
+    I: Iterator<Item = Move>,
+{
+    type Item = Result<Position>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let m = self.moves.next()?;
+        self.position = self.model.step(self.position?, &m);
+        Some(self.position.ok_or_else(out_of_range))
     }
 }
 
-#[derive(Debug, Default)]
-struct State2 {
-    state: State,
-    aim: i64,
-}
-
-impl State2 {
-    fn update(self, m: &Move) -> State2 {
-        let State2 {
-            state: State { depth, x },
-            aim,
-        } = self;
-        let (x, depth, aim) = match m {
-            Move { dir: Dir::U, amt } => (x, depth, aim - amt),
-            Move { dir: Dir::D, amt } => (x, depth, aim + amt),
-            Move { dir: Dir::F, amt } => (x + amt, depth + aim * amt, aim),
-        };
-        let state = State { depth, x };
-        State2 { state, aim }
+/// The greatest depth the submarine reaches, starting from the surface.
+pub fn max_depth<M>(model: &M, moves: impl IntoIterator<Item = Move>) -> Result<i64>
+where
+    M: SubmarineModel + ?Sized,
+{
+    trace(model, moves).try_fold(0, |max, p| Ok(max.max(p?.depth)))
+}
+
+/// The step at which the submarine is first at least `depth` deep, if any.
+///
+/// Steps count moves from 1, like the `step` column of `aoc2021 dive --trace`:
+/// step 1 is the position after the first move.
+pub fn first_reaching<M>(
+    model: &M,
+    moves: impl IntoIterator<Item = Move>,
+    depth: i64,
+) -> Result<Option<usize>>
+where
+    M: SubmarineModel + ?Sized,
+{
+    for (i, p) in trace(model, moves).enumerate() {
+        if p?.depth >= depth {
+            return Ok(Some(i + 1));
+        }
     }
+    Ok(None)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Move>> {
-    let mv = alt((
+/// A single [`Move`].
+pub fn command(input: &str) -> IResult<&str, Move> {
+    let dir = alt((
         value(Dir::F, tag("forward")),
         value(Dir::U, tag("up")),
         value(Dir::D, tag("down")),
     ));
-    let number = map_res(digit1, |e| i64::from_str_radix(e, 10));
+    map(separated_pair(dir, space1, unsigned), |(dir, amt)| Move {
+        dir,
+        amt,
+    })(input)
+}
 
-    many1(terminated(
-        map(separated_pair(mv, multispace1, number), |(dir, amt)| Move {
-            dir,
-            amt,
-        }),
-        opt(line_ending),
-    ))(input)
+/// One [`Move`] per line.
+pub fn parse(input: &str) -> IResult<&str, Vec<Move>> {
+    lines(command)(input)
 }
 
-impl Part1 {
-    pub fn solve(input: &str) -> Result<i64> {
-        let (rest, moves) = parse(input).unwrap();
-        assert!(rest.len() == 0);
+/// Parse a whole puzzle input, failing with a
+/// [`ParseError`](crate::error::ParseError) on malformed input.
+pub fn parse_input(input: &str) -> Result<Vec<Move>> {
+    Ok(parse_all(parse, input)?)
+}
 
-        let state = moves.into_iter().fold(
-            State {
-                ..Default::default()
-            },
-            |acc, el| acc.update(&el),
-        );
+/// Follow the moves read from `reader` one at a time, in constant memory.
+fn dive_reader(model: &dyn SubmarineModel, reader: impl BufRead) -> Result<Position> {
+    records(reader, command).try_fold(Position::default(), |p, m| {
+        model.step(p, &m?).ok_or_else(out_of_range)
+    })
+}
 
-        Ok(state.x * state.depth)
+impl Part1 {
+    /// Solve while reading the moves one at a time, in constant memory.
+    pub fn solve_reader(reader: impl BufRead) -> Result<i64> {
+        dive_reader(&Direct, reader)?.product()
     }
 }
 
 impl Part2 {
-    pub fn solve(input: &str) -> Result<i64> {
-        let (rest, moves) = parse(input).unwrap();
-        assert!(rest.len() == 0);
+    /// Solve while reading the moves one at a time, in constant memory.
+    pub fn solve_reader(reader: impl BufRead) -> Result<i64> {
+        dive_reader(&Aimed, reader)?.product()
+    }
+}
 
-        let state = moves.into_iter().fold(
-            State2 {
-                ..Default::default()
-            },
-            |acc, el| acc.update(&el),
-        );
+impl Puzzle for Part1 {
+    const DAY: u8 = 2;
+    const PART: u8 = 1;
+    const NAME: &'static str = "Dive!";
+
+    type Input = Vec<Move>;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Vec<Move>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(moves: &Vec<Move>) -> Result<i64> {
+        dive(&Direct, moves.iter().copied())?.product()
+    }
+}
+
+impl Puzzle for Part2 {
+    const DAY: u8 = 2;
+    const PART: u8 = 2;
+    const NAME: &'static str = "Dive!";
+
+    type Input = Vec<Move>;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Vec<Move>> {
+        parse_input(input)
+    }
 
-        let state = state.state;
-        Ok(state.x * state.depth)
+    fn solve_parsed(moves: &Vec<Move>) -> Result<i64> {
+        dive(&Aimed, moves.iter().copied())?.product()
     }
 }
 
 #[test]
 fn test_parse() {
-    let (rest, _) = parse(include_str!("../assets/day2.0.txt")).unwrap();
-    assert!(rest.len() == 0);
+    let path = crate::assets::default_dir().join("day2.0.txt");
+    let input = match std::fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(_) => return eprintln!("skipping: {} not found", path.display()),
+    };
+    let (rest, _) = parse(&input).unwrap();
+    assert!(rest.is_empty());
 }
 
 #[test]
 fn test_solve() {
     assert_eq!(
         150,
-        Part1::solve(include_str!("../assets/day2.0.test.txt")).unwrap()
+        Part1::solve(include_str!("../samples/day2.0.test.txt")).unwrap()
     );
     assert_eq!(
         900,
-        Part2::solve(include_str!("../assets/day2.0.test.txt")).unwrap()
+        Part2::solve(include_str!("../samples/day2.0.test.txt")).unwrap()
+    );
+}
+
+#[test]
+fn test_solve_reader() {
+    let input = include_str!("../samples/day2.0.test.txt");
+    assert_eq!(Part1::solve_reader(input.as_bytes()).unwrap(), 150);
+    assert_eq!(Part2::solve_reader(input.as_bytes()).unwrap(), 900);
+    assert!(Part1::solve_reader("".as_bytes()).is_err());
+    assert!(Part2::solve_reader("".as_bytes()).is_err());
+}
+
+#[test]
+fn test_out_of_range() {
+    let input = format!("down {}\nforward 2\n", i64::MAX);
+    assert!(Part1::solve(&input).is_err());
+    assert!(Part2::solve(&input).is_err());
+    assert!(Part2::solve_reader(input.as_bytes()).is_err());
+    assert!(Part1::solve(&format!("up {0}\nup {0}\n", i64::MAX)).is_err());
+}
+
+#[test]
+fn test_trace() {
+    let moves = parse_input(include_str!("../samples/day2.0.test.txt")).unwrap();
+    let positions: Vec<_> = trace(&Aimed, moves.iter().copied())
+        .map(|p| {
+            let p = p.unwrap();
+            (p.x, p.depth, p.aim)
+        })
+        .collect();
+    assert_eq!(
+        positions,
+        vec![
+            (5, 0, 0),
+            (5, 0, 5),
+            (13, 40, 5),
+            (13, 40, 2),
+            (13, 40, 10),
+            (15, 60, 10)
+        ]
+    );
+    assert_eq!(max_depth(&Aimed, moves.iter().copied()).unwrap(), 60);
+    assert_eq!(
+        first_reaching(&Aimed, moves.iter().copied(), 40).unwrap(),
+        Some(3)
     );
+    assert_eq!(
+        first_reaching(&Aimed, moves.iter().copied(), 41).unwrap(),
+        Some(6)
+    );
+    assert_eq!(
+        first_reaching(&Aimed, moves.iter().copied(), 61).unwrap(),
+        None
+    );
+
+    let moves = parse_input(&format!("down {}\nforward 2\nforward 1\n", i64::MAX)).unwrap();
+    let mut steps = trace(&Aimed, moves);
+    assert!(steps.next().unwrap().is_ok());
+    assert!(steps.next().unwrap().is_err());
+    assert!(steps.next().is_none());
+}
+
+#[test]
+fn test_models() {
+    let moves = parse_input(include_str!("../samples/day2.0.test.txt")).unwrap();
+    let products: Vec<_> = models()
+        .iter()
+        .map(|m| dive(*m, moves.iter().copied())?.product())
+        .collect::<Result<_>>()
+        .unwrap();
+    assert_eq!(products, vec![150, 900]);
+    assert_eq!(max_depth(&Direct, moves.iter().copied()).unwrap(), 10);
+    assert_eq!(model("aimed").unwrap().name(), "aimed");
+    assert!(model("drag").is_err());
 }
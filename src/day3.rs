@@ -1,57 +1,94 @@
-use std::{cell::RefCell, rc::Rc};
-
-use anyhow::Result;
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::line_ending,
-    combinator::{map, opt, value},
-    multi::{fold_many1, many1},
-    sequence::terminated,
-    IResult,
+use std::{cell::RefCell, io::BufRead, rc::Rc};
+
+use anyhow::{anyhow, bail, Result};
+use nom::{branch::alt, bytes::complete::tag, combinator::value, multi::many1, IResult};
+
+use crate::{
+    error::parse_all,
+    parsers::{lines, records},
+    solver::Puzzle,
 };
 
 pub struct Part1;
 pub struct Part2;
 
-impl Part1 {
-    pub fn solve(input: &str) -> Result<u64> {
-        /// returns (mask, gamma)
-        fn parse(input: &str) -> IResult<&str, (u64, u64)> {
-            let bits = many1(alt((value(-1i8, tag("0")), value(1, tag("1")))));
-            map(
-                fold_many1(
-                    terminated(bits, opt(line_ending)),
-                    Vec::new,
-                    |mut acc: Vec<i8>, n| {
-                        acc.resize(n.len(), 0);
-                        for (a, b) in acc.iter_mut().zip(n.iter()) {
-                            *a = *a + b;
-                        }
-                        acc
-                    },
-                ),
-                |bits| {
-                    (
-                        (1 << bits.len()) - 1,
-                        bits.into_iter()
-                            .fold(0, |acc, b| (acc << 1) + ((b > 0) as u64)),
-                    )
-                },
-            )(input)
-        }
+/// A line of the report as a list of bits, most significant first.
+pub fn bits(input: &str) -> IResult<&str, Vec<u8>> {
+    many1(alt((value(0u8, tag("0")), value(1, tag("1")))))(input)
+}
+
+/// Each line of the report as a list of [`bits`].
+pub fn parse(input: &str) -> IResult<&str, Vec<Vec<u8>>> {
+    lines(bits)(input)
+}
+
+/// Parse a whole puzzle input, failing with a
+/// [`ParseError`](crate::error::ParseError) on malformed input.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u8>>> {
+    Ok(parse_all(parse, input)?)
+}
 
-        let (rest, (mask, gamma)) = parse(input).unwrap();
-        assert!(rest.len() == 0);
-        let epsilon = !gamma & mask;
-        Ok(gamma * epsilon)
+impl Puzzle for Part1 {
+    const DAY: u8 = 3;
+    const PART: u8 = 1;
+    const NAME: &'static str = "Binary Diagnostic";
+
+    type Input = Vec<Vec<u8>>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Vec<Vec<u8>>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(report: &Vec<Vec<u8>>) -> Result<u64> {
+        let votes = report.iter().fold(Vec::new(), |acc, n| vote(acc, n));
+        power(votes)
     }
 }
 
+/// Add +1 for every one and -1 for every zero of `n` to its column's votes.
+fn vote(mut votes: Vec<i32>, n: &[u8]) -> Vec<i32> {
+    votes.resize(n.len(), 0);
+    for (a, b) in votes.iter_mut().zip(n.iter()) {
+        *a += 2 * (*b as i32) - 1;
+    }
+    votes
+}
+
+/// Gamma, the most common bits, times epsilon, the least common ones.
+fn power(votes: Vec<i32>) -> Result<u64> {
+    if votes.len() > MAX_BITS {
+        bail!("numbers wider than {} bits", MAX_BITS);
+    }
+    let mask = (1 << votes.len()) - 1;
+    let gamma = votes
+        .into_iter()
+        .fold(0, |acc, b| (acc << 1) + ((b > 0) as u64));
+    let epsilon = !gamma & mask;
+    gamma
+        .checked_mul(epsilon)
+        .ok_or_else(|| anyhow!("the power consumption overflowed"))
+}
+
+/// The widest numbers the report can hold.
+const MAX_BITS: usize = 63;
+
+impl Part1 {
+    /// Solve while reading the report a line at a time, in memory
+    /// proportional to the width of a line.
+    pub fn solve_reader(reader: impl BufRead) -> Result<u64> {
+        let votes = records(reader, bits)
+            .try_fold(Vec::new(), |acc, n| Ok::<_, anyhow::Error>(vote(acc, &n?)))?;
+        power(votes)
+    }
+}
+
+type Link = Option<Rc<RefCell<Node>>>;
+
 #[derive(Default, Debug)]
 struct Node {
     count: usize,
-    children: (Option<Rc<RefCell<Node>>>, Option<Rc<RefCell<Node>>>),
+    children: (Link, Link),
 }
 
 trait TreeAccumulator {
@@ -92,37 +129,42 @@ impl TreeAccumulator for Rc<RefCell<Node>> {
     }
 }
 
-impl Part2 {
-    pub fn solve(input: &str) -> Result<usize> {
+impl Puzzle for Part2 {
+    const DAY: u8 = 3;
+    const PART: u8 = 2;
+    const NAME: &'static str = "Binary Diagnostic";
+
+    type Input = Vec<Vec<u8>>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<u8>>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(report: &Vec<Vec<u8>>) -> Result<usize> {
+        if report.iter().any(|n| n.len() > MAX_BITS) {
+            bail!("numbers wider than {} bits", MAX_BITS);
+        }
         // Want to build a binary tree and keep track of how many children are
         // down each branch.
-        fn parse(input: &str) -> IResult<&str, (usize, usize)> {
-            let bits = many1(alt((value(0i8, tag("0")), value(1, tag("1")))));
-            map(
-                fold_many1(terminated(bits, opt(line_ending)), Node::new, |acc, n| {
-                    n.into_iter().fold(
-                        acc.clone(),
-                        |acc, b| {
-                            if b == 0 {
-                                acc.zero()
-                            } else {
-                                acc.one()
-                            }
-                        },
-                    );
-                    acc
-                }),
-                |tree| {
-                    let ox = readout(&tree, |n0, n1| n1 >= n0);
-                    let co2 = readout(&tree, |n0, n1| n1 < n0);
-                    (ox, co2)
+        let tree = Node::new();
+        for n in report {
+            n.iter().fold(
+                tree.clone(),
+                |acc, &b| {
+                    if b == 0 {
+                        acc.zero()
+                    } else {
+                        acc.one()
+                    }
                 },
-            )(input)
+            );
         }
 
-        let (rest, (ox, co2)) = parse(input).unwrap();
-        assert!(rest.len() == 0);
-        Ok(ox * co2)
+        let ox = readout(&tree, |n0, n1| n1 >= n0);
+        let co2 = readout(&tree, |n0, n1| n1 < n0);
+        ox.checked_mul(co2)
+            .ok_or_else(|| anyhow!("the life support rating overflowed"))
     }
 }
 
@@ -131,12 +173,12 @@ fn readout<F>(tree: &Rc<RefCell<Node>>, pred: F) -> usize
 where
     F: Fn(usize, usize) -> bool,
 {
-    fn count(n: &Option<Rc<RefCell<Node>>>) -> Option<usize> {
+    fn count(n: &Link) -> Option<usize> {
         n.as_ref().map(|n| n.borrow().count)
     }
     fn child_counts(n: Rc<RefCell<Node>>) -> (Option<usize>, Option<usize>) {
         let (l, r) = &n.borrow().children;
-        (count(&l), count(&r))
+        (count(l), count(r))
     }
 
     let mut out = 0;
@@ -160,11 +202,11 @@ where
 fn day3() {
     assert_eq!(
         198,
-        Part1::solve(include_str!("../assets/day3.0.test.txt")).unwrap()
+        Part1::solve(include_str!("../samples/day3.0.test.txt")).unwrap()
     );
     assert_eq!(
         230,
-        Part2::solve(include_str!("../assets/day3.0.test.txt")).unwrap()
+        Part2::solve(include_str!("../samples/day3.0.test.txt")).unwrap()
     );
 }
 
@@ -213,3 +255,21 @@ fn test_tree() {
     assert_eq!(2, acc.borrow().count);
     assert_eq!(2, acc.borrow().children.1.as_ref().unwrap().borrow().count);
 }
+
+#[test]
+fn test_solve_reader() {
+    let input = include_str!("../samples/day3.0.test.txt");
+    assert_eq!(Part1::solve_reader(input.as_bytes()).unwrap(), 198);
+    assert!(Part1::solve_reader("".as_bytes()).is_err());
+}
+
+#[test]
+fn test_wide_numbers() {
+    let wide = "1".repeat(64) + "\n";
+    assert!(Part1::solve(&wide).is_err());
+    assert!(Part1::solve_reader(wide.as_bytes()).is_err());
+    assert!(Part2::solve(&wide).is_err());
+    let big = format!("{}\n1{}\n", "1".repeat(62), "0".repeat(61));
+    assert!(Part1::solve(&big).is_err());
+    assert!(Part2::solve(&big).is_err());
+}
@@ -1,19 +1,26 @@
 use anyhow::{anyhow, Result};
+
+use crate::{error::parse_all, solver::Puzzle};
+
 pub struct Part1;
 pub struct Part2;
 
-#[derive(Debug, Default, PartialEq, Eq)]
-struct Board(Vec<Vec<u8>>);
+/// A 5x5 bingo board, row by row.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Board(pub Vec<Vec<u8>>);
 
-#[derive(Debug)]
-struct Input {
-    numbers: Vec<u8>,
-    boards: Vec<Board>,
+/// The numbers to draw, in order, and the boards in play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub numbers: Vec<u8>,
+    pub boards: Vec<Board>,
 }
 
 #[derive(Debug, Default, Clone)]
 struct State {
     unmarked_sum: u16,
+    /// One bit per cell, row by row.
+    marked: u32,
     column_hits: [u8; 5],
     row_hits: [u8; 5],
     done: bool,
@@ -22,24 +29,19 @@ struct State {
 pub mod parse {
 
     use nom::{
-        bytes::complete::tag,
-        character::{
-            complete::{digit1, multispace0},
-            streaming::line_ending,
-        },
-        combinator::{map, map_res, opt},
-        multi::{many0, many1, many_m_n},
-        sequence::{delimited, pair, terminated},
+        character::complete::{char, line_ending},
+        combinator::{map, opt},
+        multi::{count, many1, separated_list1},
+        sequence::{pair, terminated},
         IResult,
     };
 
     use super::{Board, Input};
+    use crate::parsers::{list, unsigned, ws};
 
-    fn numbers(input: &str) -> IResult<&str, Vec<u8>> {
-        many1(terminated(
-            map_res(digit1, |e: &str| e.parse::<u8>()),
-            opt(tag(",")),
-        ))(input)
+    /// Comma-separated draw order.
+    pub fn numbers(input: &str) -> IResult<&str, Vec<u8>> {
+        list(char(','), unsigned)(input)
     }
 
     #[test]
@@ -47,14 +49,10 @@ pub mod parse {
         assert_eq!(numbers("13,47,64,52").unwrap(), ("", vec![13, 47, 64, 52]));
     }
 
-    fn board(input: &str) -> IResult<&str, Board> {
-        let entry = delimited(
-            multispace0,
-            map_res(digit1, |e: &str| e.parse::<u8>()),
-            multispace0,
-        );
-        let row = many_m_n(5, 5, entry);
-        map(many_m_n(5, 5, row), Board)(input)
+    /// A single whitespace-separated [`Board`] of five rows.
+    pub fn board(input: &str) -> IResult<&str, Board> {
+        let row = terminated(count(ws(unsigned), 5), opt(line_ending));
+        map(count(row, 5), Board)(input)
     }
 
     #[test]
@@ -71,12 +69,16 @@ pub mod parse {
             vec![94, 89, 95, 2, 23],
             vec![64, 63, 45, 50, 66],
         ];
-        assert_eq!(board(&input).unwrap(), ("", Board(out)));
+        assert_eq!(board(input).unwrap(), ("", Board(out)));
     }
 
-    pub(super) fn data(input: &str) -> IResult<&str, Input> {
+    /// The draw order followed by the boards.
+    pub fn data(input: &str) -> IResult<&str, Input> {
         map(
-            pair(terminated(numbers, many0(line_ending)), many1(board)),
+            pair(
+                terminated(numbers, many1(line_ending)),
+                separated_list1(line_ending, board),
+            ),
             |(numbers, boards)| Input { numbers, boards },
         )(input)
     }
@@ -106,16 +108,44 @@ impl State {
             ..Default::default()
         }
     }
+
+    /// Mark the cell at `r`, `c`, which holds `n`. Returns whether that
+    /// completes its row or column. Marking a cell again changes nothing.
+    fn mark(&mut self, r: usize, c: usize, n: u8) -> bool {
+        let bit = 1 << (r * 5 + c);
+        if self.marked & bit != 0 {
+            return false;
+        }
+        self.marked |= bit;
+        self.unmarked_sum -= n as u16;
+        self.row_hits[r] += 1;
+        self.column_hits[c] += 1;
+        self.row_hits[r] == 5 || self.column_hits[c] == 5
+    }
 }
 
-impl Part2 {
-    pub fn solve(input: &str) -> Result<usize> {
-        let (rest, input) = parse::data(input).unwrap();
-        assert!(rest.len() == 0);
+/// Parse a whole puzzle input, failing with a
+/// [`ParseError`](crate::error::ParseError) on malformed input.
+pub fn parse_input(input: &str) -> Result<Input> {
+    Ok(parse_all(parse::data, input)?)
+}
+
+impl Puzzle for Part2 {
+    const DAY: u8 = 4;
+    const PART: u8 = 2;
+    const NAME: &'static str = "Giant Squid";
+
+    type Input = Input;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
 
+    fn solve_parsed(input: &Input) -> Result<usize> {
         let mut remaining = input.boards.len();
-        let mut states: Vec<State> = input.boards.iter().map(|b| State::new(b)).collect();
-        for n in input.numbers {
+        let mut states: Vec<State> = input.boards.iter().map(State::new).collect();
+        for &n in &input.numbers {
             for (board, state) in input
                 .boards
                 .iter()
@@ -123,10 +153,7 @@ impl Part2 {
                 .filter(|(_, s)| !s.done)
             {
                 if let Some((r, c)) = board.hit(n) {
-                    state.unmarked_sum -= n as u16;
-                    state.row_hits[r] += 1;
-                    state.column_hits[c] += 1;
-                    if state.row_hits[r] == 5 || state.column_hits[c] == 5 {
+                    if state.mark(r, c, n) {
                         state.done = true;
                         remaining -= 1;
                     }
@@ -140,19 +167,24 @@ impl Part2 {
     }
 }
 
-impl Part1 {
-    pub fn solve(input: &str) -> Result<usize> {
-        let (rest, input) = parse::data(input).unwrap();
-        assert!(rest.len() == 0);
+impl Puzzle for Part1 {
+    const DAY: u8 = 4;
+    const PART: u8 = 1;
+    const NAME: &'static str = "Giant Squid";
 
-        let mut states: Vec<State> = input.boards.iter().map(|b| State::new(b)).collect();
-        for n in input.numbers {
+    type Input = Input;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(input: &Input) -> Result<usize> {
+        let mut states: Vec<State> = input.boards.iter().map(State::new).collect();
+        for &n in &input.numbers {
             for (board, state) in input.boards.iter().zip(states.iter_mut()) {
                 if let Some((r, c)) = board.hit(n) {
-                    state.unmarked_sum -= n as u16;
-                    state.row_hits[r] += 1;
-                    state.column_hits[c] += 1;
-                    if state.row_hits[r] == 5 || state.column_hits[c] == 5 {
+                    if state.mark(r, c, n) {
                         return Ok(state.unmarked_sum as usize * n as usize);
                     }
                 }
@@ -165,10 +197,25 @@ impl Part1 {
 fn day4() {
     assert_eq!(
         4512,
-        Part1::solve(include_str!("../assets/day4.0.test.txt")).unwrap()
+        Part1::solve(include_str!("../samples/day4.0.test.txt")).unwrap()
     );
     assert_eq!(
         1924,
-        Part2::solve(include_str!("../assets/day4.0.test.txt")).unwrap()
+        Part2::solve(include_str!("../samples/day4.0.test.txt")).unwrap()
     );
 }
+
+#[test]
+fn test_windows_input() {
+    let input = include_str!("../samples/day4.0.test.txt");
+    let windows = format!("\u{feff}{}\r\n\r\n", input.replace('\n', "\r\n"));
+    assert_eq!(parse_input(&windows).unwrap(), parse_input(input).unwrap());
+}
+
+#[test]
+fn test_repeated_draws() {
+    let board = "1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25\n";
+    let input = format!("1,1,1,1,1,2,3,4,5\n\n{}", board);
+    assert_eq!(Part1::solve(&input).unwrap(), (325 - 15) * 5);
+    assert_eq!(Part2::solve(&input).unwrap(), (325 - 15) * 5);
+}
@@ -1,28 +1,30 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, line_ending},
-    combinator::{map, map_res, opt},
-    multi::many1,
-    sequence::{separated_pair, terminated},
-    IResult,
+use anyhow::{bail, Result};
+use nom::{bytes::complete::tag, combinator::map, sequence::separated_pair, IResult};
+
+use crate::{
+    error::parse_all,
+    parsers::{lines, unsigned},
+    solver::Puzzle,
 };
+
 pub struct Part1;
 pub struct Part2;
 
-#[derive(Debug)]
-struct Point((i32, i32));
-#[derive(Debug)]
-struct Line((Point, Point));
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point(pub (i32, i32));
+/// A vent line between two end points, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line(pub (Point, Point));
 
+/// The points covered by a [`Line`], from its start to its end.
 #[derive(Debug)]
-struct LinePoints {
+pub struct LinePoints {
     start: (i32, i32),
     dr: (i32, i32),
-    i: i32,
-    n: i32,
+    i: i64,
+    n: i64,
 }
 
 impl Iterator for LinePoints {
@@ -30,78 +32,134 @@ impl Iterator for LinePoints {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.i <= self.n {
-            let pt = (
-                self.start.0 + self.dr.0 * self.i,
-                self.start.1 + self.dr.1 * self.i,
-            );
+            // between the end points, so it fits
+            let i = self.i as i32;
+            let pt = (self.start.0 + self.dr.0 * i, self.start.1 + self.dr.1 * i);
             self.i += 1;
             Some(pt)
         } else {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.n + 1 - self.i).max(0) as usize;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for LinePoints {}
+
+/// The most points the lines may cover between them, several times what a real
+/// input does, so that huge lines fail rather than exhaust memory.
+const MAX_POINTS: usize = 1_000_000;
+
+/// How many points are covered by more than one of `lines`.
+fn overlaps(lines: &[&Line]) -> Result<usize> {
+    let points = lines
+        .iter()
+        .map(|l| l.iter().len())
+        .fold(0, usize::saturating_add);
+    if points > MAX_POINTS {
+        bail!(
+            "the lines cover {} points, more than the {} allowed",
+            points,
+            MAX_POINTS
+        );
+    }
+    let mut hist = HashMap::with_capacity(points);
+    for line in lines {
+        for p in line.iter() {
+            *hist.entry(p).or_insert(0) += 1;
+        }
+    }
+    Ok(hist.values().filter(|&&v| v > 1).count())
 }
 
 impl Line {
-    fn is_straight(&self) -> bool {
+    /// Is the line horizontal or vertical?
+    pub fn is_straight(&self) -> bool {
         let (Point((r0x, r0y)), Point((r1x, r1y))) = self.0;
         r0x == r1x || r0y == r1y
     }
 
-    fn iter(&self) -> LinePoints {
+    /// Is the line at 45 degrees?
+    pub fn is_diagonal(&self) -> bool {
+        let (Point((r0x, r0y)), Point((r1x, r1y))) = self.0;
+        r0x.abs_diff(r1x) == r0y.abs_diff(r1y)
+    }
+
+    pub fn iter(&self) -> LinePoints {
         let (Point((r0x, r0y)), Point((r1x, r1y))) = self.0;
         let (dx, dy) = (r1x - r0x, r1y - r0y);
 
-        // we know lines are either horizontal, vertical or diagonal
+        // only right for lines that are horizontal, vertical or diagonal
         LinePoints {
             start: (r0x, r0y),
             dr: (dx.signum(), dy.signum()),
             i: 0,
-            n: dx.abs().max(dy.abs()),
+            n: dx.abs().max(dy.abs()) as i64,
         }
     }
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Line>> {
-    fn number(input: &str) -> IResult<&str, i32> {
-        map_res(digit1, |e: &str| e.parse::<i32>())(input)
-    }
+/// One `x1,y1 -> x2,y2` line per row.
+pub fn parse(input: &str) -> IResult<&str, Vec<Line>> {
     fn point(input: &str) -> IResult<&str, Point> {
-        map(separated_pair(number, tag(","), number), Point)(input)
+        map(separated_pair(unsigned, tag(","), unsigned), Point)(input)
     }
-    let line = map(separated_pair(point, tag(" -> "), point), Line);
-    many1(terminated(line, opt(line_ending)))(input)
+    lines(map(separated_pair(point, tag(" -> "), point), Line))(input)
 }
 
-impl Part1 {
-    pub fn solve(input: &str) -> Result<usize> {
-        let (rest, lines) = parse(input).unwrap();
-        assert!(rest.len() == 0);
+/// Parse a whole puzzle input, failing with a
+/// [`ParseError`](crate::error::ParseError) on malformed input.
+pub fn parse_input(input: &str) -> Result<Vec<Line>> {
+    Ok(parse_all(parse, input)?)
+}
 
-        let mut hist = HashMap::new();
-        for line in lines.iter().filter(|&l| l.is_straight()) {
-            for p in line.iter() {
-                *hist.entry(p).or_insert(0) += 1;
-            }
-        }
+impl Puzzle for Part1 {
+    const DAY: u8 = 5;
+    const PART: u8 = 1;
+    const NAME: &'static str = "Hydrothermal Venture";
+
+    type Input = Vec<Line>;
+    type Output = usize;
 
-        Ok(hist.iter().filter(|(_, &v)| v > 1).count())
+    fn parse(input: &str) -> Result<Vec<Line>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(lines: &Vec<Line>) -> Result<usize> {
+        overlaps(&lines.iter().filter(|l| l.is_straight()).collect::<Vec<_>>())
     }
 }
 
-impl Part2 {
-    pub fn solve(input: &str) -> Result<usize> {
-        let (rest, lines) = parse(input).unwrap();
-        assert!(rest.len() == 0);
+impl Puzzle for Part2 {
+    const DAY: u8 = 5;
+    const PART: u8 = 2;
+    const NAME: &'static str = "Hydrothermal Venture";
+
+    type Input = Vec<Line>;
+    type Output = usize;
 
-        let mut hist = HashMap::new();
+    fn parse(input: &str) -> Result<Vec<Line>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(lines: &Vec<Line>) -> Result<usize> {
         for line in lines.iter() {
-            for p in line.iter() {
-                *hist.entry(p).or_insert(0) += 1;
+            if !line.is_straight() && !line.is_diagonal() {
+                let (Point((x1, y1)), Point((x2, y2))) = line.0;
+                bail!(
+                    "line {},{} -> {},{} is neither straight nor diagonal",
+                    x1,
+                    y1,
+                    x2,
+                    y2
+                );
             }
         }
-
-        Ok(hist.iter().filter(|(_, &v)| v > 1).count())
+        overlaps(&lines.iter().collect::<Vec<_>>())
     }
 }
 
@@ -109,10 +167,33 @@ impl Part2 {
 fn day5() {
     assert_eq!(
         5,
-        Part1::solve(include_str!("../assets/day5.0.test.txt")).unwrap()
+        Part1::solve(include_str!("../samples/day5.0.test.txt")).unwrap()
     );
     assert_eq!(
         12,
-        Part2::solve(include_str!("../assets/day5.0.test.txt")).unwrap()
+        Part2::solve(include_str!("../samples/day5.0.test.txt")).unwrap()
     );
 }
+
+#[test]
+fn test_parse_error() {
+    let err = Part1::solve("0,9 -> 5,9\n8,0 => 0,8\n").unwrap_err();
+    let err = err.downcast::<crate::error::ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (2, 1));
+    assert_eq!(err.snippet, "8,0 => 0,8");
+}
+
+#[test]
+fn test_skewed_line() {
+    let input = "0,9 -> 5,9\n0,0 -> 2,1\n";
+    assert_eq!(Part1::solve(input).unwrap(), 0);
+    assert!(Part2::solve(input).is_err());
+}
+
+#[test]
+fn test_huge_lines() {
+    assert!(Part1::solve("0,0 -> 2000000000,0\n").is_err());
+    assert!(Part2::solve("0,0 -> 2000000000,2000000000\n").is_err());
+    assert_eq!(Part1::solve("0,0 -> 999,0\n0,0 -> 0,999\n").unwrap(), 1);
+    assert_eq!(Line((Point((3, 0)), Point((0, 3)))).iter().len(), 4);
+}
@@ -1,45 +1,118 @@
-use anyhow::Result;
+use std::io::BufRead;
+
+use anyhow::{bail, Context, Result};
 use nom::{
-    bytes::complete::tag,
-    character::complete::digit1,
-    combinator::{map_res, opt},
-    multi::many1,
+    character::complete::{char, line_ending},
+    combinator::opt,
     sequence::terminated,
     IResult,
 };
 
+use crate::{
+    error::parse_all,
+    parsers::{list, unsigned},
+    solver::Puzzle,
+};
+
 pub struct Part1;
 
 pub struct Part2;
 
-fn parse(input: &str) -> IResult<&str, Vec<u8>> {
-    many1(terminated(
-        map_res(digit1, |e: &str| e.parse::<u8>()),
-        opt(tag(",")),
-    ))(input)
+/// Comma-separated fish timers.
+pub fn parse(input: &str) -> IResult<&str, Vec<u8>> {
+    terminated(list(char(','), unsigned), opt(line_ending))(input)
 }
 
-impl Part1 {
-    pub fn solve(input: &str) -> Result<usize> {
-        let (_rest, fish) = parse(input).unwrap();
+/// Parse a whole puzzle input, failing with a
+/// [`ParseError`](crate::error::ParseError) on malformed input.
+pub fn parse_input(input: &str) -> Result<Vec<u8>> {
+    Ok(parse_all(parse, input)?)
+}
+
+/// How many fish have each timer value, read from comma-separated timers
+/// one at a time.
+fn read_histogram(reader: impl BufRead) -> Result<[usize; 9]> {
+    let mut counts = [0; 9];
+    for (i, timer) in reader.split(b',').enumerate() {
+        let timer = timer?;
+        let timer = std::str::from_utf8(&timer)
+            .ok()
+            .map(|t| t.trim_start_matches('\u{feff}').trim())
+            .with_context(|| format!("invalid timer {}", i + 1))?;
+        match timer.parse::<usize>() {
+            Ok(t) if t < counts.len() => counts[t] += 1,
+            _ => bail!("invalid timer {} {:?}, expected 0 to 8", i + 1, timer),
+        }
+    }
+    if counts.iter().all(|&n| n == 0) {
+        bail!("there are no timers");
+    }
+    Ok(counts)
+}
 
-        Ok(simulate(fish, 80))
+impl Part1 {
+    /// Solve while reading the timers one at a time, in constant memory.
+    pub fn solve_reader(reader: impl BufRead) -> Result<usize> {
+        Ok(grow(read_histogram(reader)?, 80))
     }
 }
 
 impl Part2 {
-    pub fn solve(input: &str) -> Result<usize> {
-        let (_rest, fish) = parse(input).unwrap();
+    /// Solve while reading the timers one at a time, in constant memory.
+    pub fn solve_reader(reader: impl BufRead) -> Result<usize> {
+        Ok(grow(read_histogram(reader)?, 256))
+    }
+}
+
+impl Puzzle for Part1 {
+    const DAY: u8 = 6;
+    const PART: u8 = 1;
+    const NAME: &'static str = "Lanternfish";
+
+    type Input = Vec<u8>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Vec<u8>> {
+        parse_input(input)
+    }
 
-        Ok(simulate(fish, 256))
+    fn solve_parsed(fish: &Vec<u8>) -> Result<usize> {
+        simulate(fish, 80)
     }
 }
 
-fn simulate(fish: Vec<u8>, days: usize) -> usize {
-    let mut counts: Vec<_> = vec![0; 9];
-    for f in fish {
-        counts[f as usize] += 1;
+impl Puzzle for Part2 {
+    const DAY: u8 = 6;
+    const PART: u8 = 2;
+    const NAME: &'static str = "Lanternfish";
+
+    type Input = Vec<u8>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Vec<u8>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(fish: &Vec<u8>) -> Result<usize> {
+        simulate(fish, 256)
     }
+}
+
+/// How many fish are there after `days`? Fails on timers above 8.
+pub fn simulate(fish: &[u8], days: usize) -> Result<usize> {
+    let mut counts = [0; 9];
+    for (i, &f) in fish.iter().enumerate() {
+        match counts.get_mut(f as usize) {
+            Some(count) => *count += 1,
+            None => bail!("invalid timer {} {:?}, expected 0 to 8", i + 1, f),
+        }
+    }
+    Ok(grow(counts, days))
+}
+
+/// How many fish are there after `days`, given how many have each timer
+/// value now?
+fn grow(mut counts: [usize; 9], days: usize) -> usize {
     for _day in 0..days {
         let dividing = counts[0];
         counts.rotate_left(1);
@@ -52,10 +125,27 @@ fn simulate(fish: Vec<u8>, days: usize) -> usize {
 fn day6() {
     assert_eq!(
         5934,
-        Part1::solve(include_str!("../assets/day6.0.test.txt")).unwrap()
+        Part1::solve(include_str!("../samples/day6.0.test.txt")).unwrap()
     );
     assert_eq!(
         26984457539,
-        Part2::solve(include_str!("../assets/day6.0.test.txt")).unwrap()
+        Part2::solve(include_str!("../samples/day6.0.test.txt")).unwrap()
     );
 }
+
+#[test]
+fn test_solve_reader() {
+    let input = include_str!("../samples/day6.0.test.txt");
+    assert_eq!(Part1::solve_reader(input.as_bytes()).unwrap(), 5934);
+    assert_eq!(Part2::solve_reader(input.as_bytes()).unwrap(), 26984457539);
+    assert!(Part1::solve_reader("3,9\n".as_bytes()).is_err());
+    assert!(Part1::solve_reader("3,,4\n".as_bytes()).is_err());
+    assert!(Part1::solve_reader("".as_bytes()).is_err());
+    assert!(Part2::solve_reader("".as_bytes()).is_err());
+}
+
+#[test]
+fn test_invalid_timer() {
+    assert!(Part1::solve("3,4,9\n").is_err());
+    assert_eq!(simulate(&[3, 4, 3, 1, 2], 18).unwrap(), 26);
+}
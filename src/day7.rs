@@ -1,33 +1,55 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use nom::{
-    bytes::complete::tag,
-    character::complete::digit1,
-    combinator::{map_res, opt},
-    multi::many1,
+    character::complete::{char, line_ending},
+    combinator::opt,
     sequence::terminated,
     IResult,
 };
 
+use crate::{
+    error::parse_all,
+    parsers::{list, unsigned},
+    solver::Puzzle,
+};
+
 pub struct Part1;
 pub struct Part2;
 
-fn parse(input: &str) -> IResult<&str, Vec<u16>> {
-    many1(terminated(
-        map_res(digit1, |e: &str| e.parse::<u16>()),
-        opt(tag(",")),
-    ))(input)
+/// Comma-separated crab positions.
+pub fn parse(input: &str) -> IResult<&str, Vec<u16>> {
+    terminated(list(char(','), unsigned), opt(line_ending))(input)
+}
+
+/// Parse a whole puzzle input, failing with a
+/// [`ParseError`](crate::error::ParseError) on malformed input.
+pub fn parse_input(input: &str) -> Result<Vec<u16>> {
+    Ok(parse_all(parse, input)?)
+}
+
+fn no_crabs() -> anyhow::Error {
+    anyhow!("there are no crabs")
 }
 
-impl Part1 {
-    pub fn solve(input: &str) -> Result<u32> {
-        let (_rest, crabs) = parse(input).unwrap();
-        let w = *crabs.iter().max().unwrap() as usize + 1;
-        let counts = crabs.iter().fold(vec![0u32; w], |mut acc, &c| {
+impl Puzzle for Part1 {
+    const DAY: u8 = 7;
+    const PART: u8 = 1;
+    const NAME: &'static str = "The Treachery of Whales";
+
+    type Input = Vec<u16>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Vec<u16>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(crabs: &Vec<u16>) -> Result<u64> {
+        let w = *crabs.iter().max().ok_or_else(no_crabs)? as usize + 1;
+        let counts = crabs.iter().fold(vec![0u64; w], |mut acc, &c| {
             acc[c as usize] += 1;
             acc
         });
 
-        let mut left_scores = vec![0u32; w];
+        let mut left_scores = vec![0u64; w];
         let mut left = counts.clone();
         for i in 1..left.len() {
             left[i] += left[i - 1];
@@ -36,7 +58,7 @@ impl Part1 {
             left_scores[i] = left_scores[i - 1] + left[i - 1];
         }
 
-        let mut right_scores = vec![0u32; w];
+        let mut right_scores = vec![0u64; w];
         let mut right = counts.clone();
         for i in (0..right.len() - 1).rev() {
             right[i] += right[i + 1];
@@ -45,20 +67,30 @@ impl Part1 {
             right_scores[i] += right_scores[i + 1] + right[i + 1];
         }
 
-        Ok(left_scores
+        left_scores
             .into_iter()
-            .zip(right_scores.into_iter())
+            .zip(right_scores)
             .map(|(l, r)| l + r)
             .min()
-            .unwrap())
+            .ok_or_else(no_crabs)
     }
 }
 
-impl Part2 {
-    pub fn solve(input: &str) -> Result<u32> {
-        let (_rest, crabs) = parse(input).unwrap();
-        let w = *crabs.iter().max().unwrap() as usize + 1;
-        let counts = crabs.iter().fold(vec![0u32; w], |mut acc, &c| {
+impl Puzzle for Part2 {
+    const DAY: u8 = 7;
+    const PART: u8 = 2;
+    const NAME: &'static str = "The Treachery of Whales";
+
+    type Input = Vec<u16>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Vec<u16>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(crabs: &Vec<u16>) -> Result<u64> {
+        let w = *crabs.iter().max().ok_or_else(no_crabs)? as usize + 1;
+        let counts = crabs.iter().fold(vec![0u64; w], |mut acc, &c| {
             acc[c as usize] += 1;
             acc
         });
@@ -70,7 +102,7 @@ impl Part2 {
         //       |
         // 0 1 3-6
 
-        let mut left_scores = vec![0u32; w];
+        let mut left_scores = vec![0u64; w];
         let mut left = counts.clone();
         for i in 1..left.len() {
             left[i] += left[i - 1];
@@ -82,7 +114,7 @@ impl Part2 {
             left_scores[i] += left_scores[i - 1];
         }
 
-        let mut right_scores = vec![0u32; w];
+        let mut right_scores = vec![0u64; w];
         let mut right = counts.clone();
         for i in (0..right.len() - 1).rev() {
             right[i] += right[i + 1];
@@ -94,12 +126,12 @@ impl Part2 {
             right_scores[i] += right_scores[i + 1];
         }
 
-        Ok(left_scores
+        left_scores
             .into_iter()
-            .zip(right_scores.into_iter())
+            .zip(right_scores)
             .map(|(l, r)| l + r)
             .min()
-            .unwrap())
+            .ok_or_else(no_crabs)
     }
 }
 
@@ -107,10 +139,29 @@ impl Part2 {
 fn day7() {
     assert_eq!(
         37,
-        Part1::solve(include_str!("../assets/day7.0.test.txt")).unwrap()
+        Part1::solve(include_str!("../samples/day7.0.test.txt")).unwrap()
     );
     assert_eq!(
         168,
-        Part2::solve(include_str!("../assets/day7.0.test.txt")).unwrap()
+        Part2::solve(include_str!("../samples/day7.0.test.txt")).unwrap()
+    );
+}
+
+#[test]
+fn test_far_apart() {
+    assert_eq!(
+        Part1::solve("0,0,0,65535,65535,65535\n").unwrap(),
+        3 * 65535
+    );
+    assert_eq!(
+        Part2::solve("0,0,0,65535,65535,65535\n").unwrap(),
+        3 * (32767 * 32768 / 2 + 32768 * 32769 / 2)
     );
 }
+
+#[test]
+fn test_no_crabs() {
+    assert!(Part1::solve_parsed(&vec![]).is_err());
+    assert!(Part2::solve_parsed(&vec![]).is_err());
+    assert_eq!(Part2::solve_parsed(&vec![4]).unwrap(), 0);
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+
+use nom::{error::ErrorKind, Err, IResult};
+
+use crate::parsers::normalize;
+
+/// A parse failure located in the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// The offending line.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Locate `remaining`, a suffix of `input`, in `input`.
+    fn at(input: &str, remaining: &str, message: impl Into<String>) -> Self {
+        let offset = input.len() - remaining.len();
+        let (before, after) = input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        let snippet = before[line_start..].to_string() + after.lines().next().unwrap_or("");
+        ParseError {
+            line,
+            column,
+            snippet: snippet.trim_end_matches('\r').to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "    {}", self.snippet)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "expected a number".into(),
+        ErrorKind::Tag | ErrorKind::Char | ErrorKind::OneOf => "unexpected character".into(),
+        ErrorKind::MapRes => "number out of range".into(),
+        ErrorKind::Count | ErrorKind::ManyMN => "wrong number of entries".into(),
+        ErrorKind::Eof => "expected end of input".into(),
+        kind => format!("parse error ({})", kind.description()),
+    }
+}
+
+/// Run `parser` over all of `input`, after [`normalize`]-ing it.
+///
+/// Parser failures and any input left over afterwards are reported as a
+/// [`ParseError`] pointing at the offending line and column.
+pub fn parse_all<O, F>(parser: F, input: &str) -> Result<O, ParseError>
+where
+    F: FnMut(&str) -> IResult<&str, O>,
+{
+    run(parser, &normalize(input))
+}
+
+/// Run `parser` over all of `line`, which is line `number` of a larger input
+/// that is read a line at a time.
+pub fn parse_line<O, F>(parser: F, line: &str, number: usize) -> Result<O, ParseError>
+where
+    F: FnMut(&str) -> IResult<&str, O>,
+{
+    run(parser, line).map_err(|e| ParseError { line: number, ..e })
+}
+
+fn run<O, F>(mut parser: F, input: &str) -> Result<O, ParseError>
+where
+    F: FnMut(&str) -> IResult<&str, O>,
+{
+    match parser(input) {
+        Ok(("", out)) => Ok(out),
+        Ok((rest, _)) => Err(ParseError::at(input, rest, "unexpected input")),
+        Err(Err::Error(e) | Err::Failure(e)) => {
+            Err(ParseError::at(input, e.input, describe(e.code)))
+        }
+        Err(Err::Incomplete(_)) => Err(ParseError::at(input, "", "unexpected end of input")),
+    }
+}
+
+#[test]
+fn test_parse_all() {
+    use crate::parsers::{lines, unsigned};
+
+    fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
+        lines(unsigned)(input)
+    }
+    assert_eq!(parse_all(numbers, "1\n2\n"), Ok(vec![1, 2]));
+    assert_eq!(parse_all(numbers, "\u{feff}1\r\n2\r\n\r\n"), Ok(vec![1, 2]));
+
+    let e = parse_all(numbers, "1\n2\n34x5\n").unwrap_err();
+    assert_eq!((e.line, e.column), (3, 3));
+    assert_eq!(e.snippet, "34x5");
+    assert_eq!(e.message, "unexpected input");
+
+    let e = parse_all(numbers, "1\r\n\r\n2\r\n").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 1));
+    assert_eq!(e.message, "unexpected input");
+
+    let e = parse_all(numbers, "\r\n").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 1));
+    assert_eq!(e.snippet, "");
+    assert_eq!(e.message, "expected a number");
+}
+
+#[test]
+fn test_display() {
+    let e = ParseError {
+        line: 2,
+        column: 3,
+        snippet: "ab?d".into(),
+        message: "unexpected character".into(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "line 2, column 3: unexpected character\n    ab?d\n      ^"
+    );
+}
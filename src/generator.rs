@@ -0,0 +1,245 @@
+//! Random puzzle inputs in the same text format as the real ones.
+//!
+//! The same day, seed and size always give the same input.
+
+use std::{collections::HashSet, fmt::Write as _};
+
+use anyhow::{bail, Result};
+
+/// A small, fast pseudo-random number generator (SplitMix64).
+///
+/// Not suitable for anything but making up test data.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        match (hi - lo).checked_add(1) {
+            Some(n) => lo + self.next_u64() % n,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Put `xs` in a random order.
+    pub fn shuffle<T>(&mut self, xs: &mut [T]) {
+        for i in (1..xs.len()).rev() {
+            xs.swap(i, self.range(0, i as u64) as usize);
+        }
+    }
+}
+
+/// A size close to that of the real input of `day`.
+pub fn default_size(day: u8) -> Option<usize> {
+    Some(match day {
+        1 => 2000,
+        2 => 1000,
+        3 => 1000,
+        4 => 100,
+        5 => 500,
+        6 => 300,
+        7 => 1000,
+        8 => 200,
+        _ => return None,
+    })
+}
+
+/// A random input for `day` with `size` records: measurements, commands,
+/// report lines, boards, vent lines, fish, crabs or notes.
+pub fn generate(day: u8, seed: u64, size: usize) -> Result<String> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+    Ok(match day {
+        1 => depths(rng, size),
+        2 => commands(rng, size),
+        3 => report(rng, size),
+        4 => bingo(rng, size),
+        5 => vents(rng, size),
+        6 => timers(rng, size),
+        7 => crabs(rng, size),
+        8 => notes(rng, size),
+        _ => bail!("no generator for day {}", day),
+    })
+}
+
+/// A random walk of depths that mostly goes down.
+fn depths(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100, 200) as i64;
+    let mut out = String::new();
+    for _ in 0..size {
+        let _ = writeln!(out, "{}", depth);
+        depth = (depth + rng.range(0, 50) as i64 - 20).max(0);
+    }
+    out
+}
+
+/// Commands that never take the submarine above the surface.
+fn commands(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+    let mut out = String::new();
+    for _ in 0..size {
+        let amount = rng.range(1, 9);
+        let dir = match rng.range(0, 2) {
+            0 if depth >= amount => {
+                depth -= amount;
+                "up"
+            }
+            0 | 1 => {
+                depth += amount;
+                "down"
+            }
+            _ => "forward",
+        };
+        let _ = writeln!(out, "{} {}", dir, amount);
+    }
+    out
+}
+
+/// Distinct binary numbers, wide enough for `size` of them to be sparse.
+fn report(rng: &mut Rng, size: usize) -> String {
+    let width = (usize::BITS - size.leading_zeros() + 2).max(5);
+    let mut seen = HashSet::new();
+    let mut out = String::new();
+    while seen.len() < size {
+        let n = rng.range(0, (1 << width) - 1);
+        if seen.insert(n) {
+            let _ = writeln!(out, "{:0width$b}", n, width = width as usize);
+        }
+    }
+    out
+}
+
+/// Every number from 0 to 99 drawn in a random order, and `size` boards.
+fn bingo(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u64> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let draws: Vec<_> = numbers.iter().map(u64::to_string).collect();
+    let mut out = draws.join(",") + "\n";
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        out.push('\n');
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<_> = row.iter().map(|n| format!("{:>2}", n)).collect();
+            let _ = writeln!(out, "{}", row.join(" "));
+        }
+    }
+    out
+}
+
+/// Horizontal, vertical and diagonal lines in a 1000x1000 grid.
+fn vents(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let (x1, y1) = (rng.range(0, 999) as i64, rng.range(0, 999) as i64);
+        let (dx, dy) = match rng.range(0, 3) {
+            0 => (1, 0),
+            1 => (0, 1),
+            2 => (1, 1),
+            _ => (1, -1),
+        };
+        let (dx, dy) = if rng.range(0, 1) == 0 {
+            (dx, dy)
+        } else {
+            (-dx, -dy)
+        };
+        let inside = |x: i64, y: i64| (0..1000).contains(&x) && (0..1000).contains(&y);
+        let mut n = rng.range(0, 300) as i64;
+        while !inside(x1 + dx * n, y1 + dy * n) {
+            n -= 1;
+        }
+        let _ = writeln!(out, "{},{} -> {},{}", x1, y1, x1 + dx * n, y1 + dy * n);
+    }
+    out
+}
+
+/// Fish timers from 0 to 8.
+fn timers(rng: &mut Rng, size: usize) -> String {
+    let timers: Vec<_> = (0..size).map(|_| rng.range(0, 8).to_string()).collect();
+    timers.join(",") + "\n"
+}
+
+/// Crab positions from 0 to 1999.
+fn crabs(rng: &mut Rng, size: usize) -> String {
+    let crabs: Vec<_> = (0..size).map(|_| rng.range(0, 1999).to_string()).collect();
+    crabs.join(",") + "\n"
+}
+
+/// Notes on displays whose wires are each mixed up differently.
+fn notes(rng: &mut Rng, size: usize) -> String {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut wires: Vec<char> = "abcdefg".chars().collect();
+        rng.shuffle(&mut wires);
+        let pattern = |rng: &mut Rng, digit: &str| {
+            let mut p: Vec<_> = digit
+                .bytes()
+                .map(|segment| wires[(segment - b'a') as usize])
+                .collect();
+            rng.shuffle(&mut p);
+            p.into_iter().collect::<String>()
+        };
+        let mut order: Vec<_> = (0..10).collect();
+        rng.shuffle(&mut order);
+        let patterns: Vec<_> = order.iter().map(|&d| pattern(rng, DIGITS[d])).collect();
+        let outputs: Vec<_> = (0..4)
+            .map(|_| {
+                let digit = DIGITS[rng.range(0, 9) as usize];
+                pattern(rng, digit)
+            })
+            .collect();
+        let _ = writeln!(out, "{} | {}", patterns.join(" "), outputs.join(" "));
+    }
+    out
+}
+
+#[test]
+fn test_generate() {
+    // days added since have no generator yet
+    let generated = crate::solvers()
+        .iter()
+        .filter(|s| default_size(s.day()).is_some());
+    for s in generated {
+        for seed in 0..5 {
+            let input = generate(s.day(), seed, 50).unwrap();
+            assert_eq!(input, generate(s.day(), seed, 50).unwrap());
+            if let Err(e) = s.solve(&input) {
+                panic!(
+                    "day{} part{} seed {}: {:#}\n{}",
+                    s.day(),
+                    s.part(),
+                    seed,
+                    e,
+                    input
+                );
+            }
+        }
+    }
+    assert!(generate(26, 0, 10).is_err());
+}
+
+#[test]
+fn test_rng() {
+    let mut rng = Rng::new(7);
+    let mut xs: Vec<_> = (0..20).collect();
+    rng.shuffle(&mut xs);
+    assert_ne!(xs, (0..20).collect::<Vec<_>>());
+    xs.sort_unstable();
+    assert_eq!(xs, (0..20).collect::<Vec<_>>());
+    assert!((0..1000).all(|_| (3..=5).contains(&rng.range(3, 5))));
+    assert_eq!(Rng::new(1).next_u64(), Rng::new(1).next_u64());
+}
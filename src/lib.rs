@@ -0,0 +1,37 @@
+//! Solutions to [Advent of Code 2021](https://adventofcode.com/2021).
+//!
+//! Each `dayN` module exposes its nom parsers, the parsed domain types and a
+//! `Part1`/`Part2` implementing [`Puzzle`]. Every implemented puzzle part is
+//! listed by [`solvers`].
+//!
+//! ```
+//! use aoc2021::{day1, Puzzle};
+//!
+//! let depths = day1::parse_input("199\n200\n208\n").unwrap();
+//! assert_eq!(day1::Part1::solve_parsed(&depths).unwrap(), 2);
+//! ```
+
+pub mod answer;
+pub mod assets;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod error;
+pub mod generator;
+pub mod manifest;
+pub mod memory;
+pub mod parsers;
+pub mod pool;
+pub mod reference;
+pub mod report;
+pub mod scaffold;
+pub mod solver;
+
+pub use answer::Answer;
+pub use error::ParseError;
+pub use solver::{solvers, Puzzle, Solver};
@@ -1,41 +1,670 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-
-macro_rules! problems {
-    ()=>{};
-    ($day:tt $($parts:ident)+, $($rest:tt)*) => {
-        problems!($day $($parts)+);
-        problems!($($rest)*);
-    };
-    ($day:tt $part:tt $($rest:tt)+) => {
-        problems!($day $part);
-        problems!($day $($rest)+);
-    };
-    ($day:tt $part:tt)=>{
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use aoc2021::{
+    assets, day1, day2, generator,
+    manifest::Manifest,
+    memory::CountingAllocator,
+    pool,
+    report::{self, Format, Record},
+    scaffold, solvers, Solver,
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+usage: aoc2021 [run] [--day N] [--part P] [--input FILE] [--format FORMAT] [--stats]
+                    [--jobs N]
+       aoc2021 verify [--day N] [--part P] [--assets DIR] [--manifest FILE]
+                      [--record]
+       aoc2021 generate --day N [--seed S] [--size K]
+       aoc2021 sonar [--input FILE] [--window K] [--non-decreasing] [--report]
+       aoc2021 dive [--input FILE] [--model MODEL] [--trace]
+       aoc2021 list
+       aoc2021 new-day N [NAME]
+
+run     Solve the selected puzzles. Without --input each day is run against
+        all of its dayN.K.txt inputs in the assets directory, side by side.
+        Pass '-' as FILE to read the input from stdin.
+verify  Solve every sample and every input in the assets directory and
+        compare the answers with the manifest and the inputs' .answers
+        files. Fails if any answer differs or is missing. With --record,
+        add the missing answers to the manifest and the samples' .answers
+        files instead.
+generate
+        Print a random input for day N with K records, the same for the
+        same seed.
+sonar   Count how many sums of K consecutive depths in a day 1 input are
+        larger than the sum before. The depths may be integers or decimals
+        like 1.5. Reads stdin without --input. With
+        --report, describe the depths instead: their longest increasing run,
+        largest jump, increases and sums over windows of K.
+dive    Follow the submarine commands of a day 2 input as MODEL interprets
+        them and print where the submarine ends up, or with --trace where it
+        is after each command, as CSV. Reads stdin without --input.
+list    List the available solvers.
+new-day Create src/dayN.rs with unimplemented parts and a failing example
+        test, an empty example input in samples/, and register the day.
+
+options:
+    -d, --day N         only run day N; repeat or separate days with
+                        commas to run several, e.g. --day 1,3,5
+    -p, --part P        only run part P; several work like --day
+    -i, --input FILE    solve FILE instead of the day's input (needs --day)
+    -a, --assets DIR    directory holding the inputs [default: assets in
+                        the crate directory]
+    -m, --manifest FILE expected answers [default: answers.txt in the crate
+                        directory]
+    --record            add missing answers to the manifest, or for the
+                        samples to their .answers files, in verify
+    -f, --format FORMAT text, json or csv [default: text]
+    -s, --stats         also print parse and solve times and allocations
+                        (always included in json and csv)
+    --seed S            seed for generate [default: 0]
+    --size K            number of records for generate [default: about
+                        the size of the real input]
+    -w, --window K      window size for sonar [default: 1]
+    --non-decreasing    also count equal sums in sonar
+    -r, --report        print a report on the depths in sonar
+    --model MODEL       direct (part 1) or aimed (part 2) for dive
+                        [default: aimed]
+    -t, --trace         print every position in dive
+    -j, --jobs N        solve on N threads, or one per CPU if N is 0
+                        [default: 1]
+    -h, --help          print this message";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    /// The days to run, or all if empty.
+    days: Vec<u8>,
+    /// The parts to run, or all if empty.
+    parts: Vec<u8>,
+    input: Option<String>,
+    assets: Option<PathBuf>,
+    manifest: Option<PathBuf>,
+    format: Format,
+    stats: bool,
+    jobs: Option<usize>,
+    seed: Option<u64>,
+    size: Option<usize>,
+    window: Option<usize>,
+    non_decreasing: bool,
+    report: bool,
+    model: Option<String>,
+    trace: bool,
+    record: bool,
+}
+
+enum Command {
+    Run(Options),
+    Verify(Options),
+    Generate(Options),
+    Sonar(Options),
+    Dive(Options),
+    List,
+    NewDay(u8, String),
+    Help,
+}
+
+fn parse_args<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    fn value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String> {
+        args.next()
+            .ok_or_else(|| anyhow!("missing value for {}", flag))
+    }
+    fn number<T: FromStr>(v: String, flag: &str) -> Result<T>
+    where
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        v.parse()
+            .with_context(|| format!("invalid value for {}: {:?}", flag, v))
+    }
+    fn numbers<T: FromStr>(v: String, flag: &str) -> Result<Vec<T>>
+    where
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        v.split(',').map(|n| number(n.to_string(), flag)).collect()
+    }
+
+    let mut args = args.into_iter().peekable();
+    let command: fn(Options) -> Command = match args.peek().map(String::as_str) {
+        Some("run") => {
+            args.next();
+            Command::Run
+        }
+        Some("verify") => {
+            args.next();
+            Command::Verify
+        }
+        Some("generate") => {
+            args.next();
+            Command::Generate
+        }
+        Some("sonar") => {
+            args.next();
+            Command::Sonar
+        }
+        Some("dive") => {
+            args.next();
+            Command::Dive
+        }
+        Some("list") => return Ok(Command::List),
+        Some("new-day") => {
+            args.next();
+            let day = number(value(&mut args, "new-day")?, "new-day")?;
+            let name = args.collect::<Vec<_>>().join(" ");
+            return Ok(Command::NewDay(day, name));
+        }
+        _ => Command::Run,
+    };
+
+    let mut opts = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => opts
+                .days
+                .extend(numbers::<u8>(value(&mut args, &arg)?, &arg)?),
+            "-p" | "--part" => opts
+                .parts
+                .extend(numbers::<u8>(value(&mut args, &arg)?, &arg)?),
+            "-i" | "--input" => opts.input = Some(value(&mut args, &arg)?),
+            "-a" | "--assets" => opts.assets = Some(value(&mut args, &arg)?.into()),
+            "-m" | "--manifest" => opts.manifest = Some(value(&mut args, &arg)?.into()),
+            "-f" | "--format" => opts.format = value(&mut args, &arg)?.parse()?,
+            "-s" | "--stats" => opts.stats = true,
+            "--seed" => opts.seed = Some(number(value(&mut args, &arg)?, &arg)?),
+            "--size" => opts.size = Some(number(value(&mut args, &arg)?, &arg)?),
+            "-j" | "--jobs" => opts.jobs = Some(number(value(&mut args, &arg)?, &arg)?),
+            "-w" | "--window" => opts.window = Some(number(value(&mut args, &arg)?, &arg)?),
+            "--non-decreasing" => opts.non_decreasing = true,
+            "-r" | "--report" => opts.report = true,
+            "--model" => opts.model = Some(value(&mut args, &arg)?),
+            "-t" | "--trace" => opts.trace = true,
+            "--record" => opts.record = true,
+            "-h" | "--help" | "help" => return Ok(Command::Help),
+            _ => bail!("unexpected argument {:?}\n\n{}", arg, USAGE),
+        }
+    }
+    let command = command(opts);
+    if let Command::Run(opts) | Command::Verify(opts) | Command::Generate(opts) = &command {
+        if opts.input.is_some() && opts.days.is_empty() {
+            bail!("--input requires --day");
+        }
+    }
+    Ok(command)
+}
+
+fn read_input(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .context("could not read stdin")?;
+        Ok(buf)
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("could not read {}", path))
+    }
+}
+
+/// The registered solvers matching the day and part filters.
+fn select(opts: &Options) -> Result<Vec<&'static dyn Solver>> {
+    let selected: Vec<_> = solvers()
+        .iter()
+        .copied()
+        .filter(|s| opts.days.is_empty() || opts.days.contains(&s.day()))
+        .filter(|s| opts.parts.is_empty() || opts.parts.contains(&s.part()))
+        .collect();
+    if selected.is_empty() {
+        bail!("no solver matches the selection");
+    }
+    Ok(selected)
+}
+
+/// A named puzzle input.
+#[derive(Clone)]
+struct Input {
+    name: String,
+    text: String,
+}
+
+fn run(opts: Options) -> Result<()> {
+    let selected = select(&opts)?;
+    let given = match opts.input.as_deref() {
+        Some(path) => Some(Input {
+            name: if path == "-" { "stdin" } else { path }.to_string(),
+            text: read_input(path)?,
+        }),
+        None => None,
+    };
+    let dir = opts.assets.clone().unwrap_or_else(assets::default_dir);
+    let discovered = match given {
+        Some(_) => Vec::new(),
+        None => assets::discover(&dir).context("check out the assets submodule or pass --input")?,
+    };
+
+    let mut days: Vec<_> = selected.iter().map(|s| s.day()).collect();
+    days.dedup();
+    let mut all_inputs = Vec::new();
+    for day in days {
+        let inputs = match &given {
+            Some(input) => vec![input.clone()],
+            None => discovered
+                .iter()
+                .filter(|f| f.day == day && !f.example)
+                .map(|f| {
+                    Ok(Input {
+                        name: f.name().to_string(),
+                        text: std::fs::read_to_string(&f.path)
+                            .with_context(|| format!("could not read {}", f.path.display()))?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+        if inputs.is_empty() {
+            eprintln!("day{}: no inputs in {}", day, dir.display());
+            continue;
+        }
+        all_inputs.extend(inputs.into_iter().map(|input| (day, input)));
+    }
+    if all_inputs.is_empty() {
+        bail!("no inputs found in {}", dir.display());
+    }
+
+    let jobs: Vec<_> = selected
+        .iter()
+        .flat_map(|&s| {
+            all_inputs
+                .iter()
+                .filter(move |(day, _)| *day == s.day())
+                .map(move |(_, input)| (s, input))
+        })
+        .collect();
+    let threads = pool::threads(opts.jobs.unwrap_or(1));
+    let records = pool::map(&jobs, threads, |(s, input)| {
+        Record::run(*s, &input.name, &input.text)
+    });
+    let mut out = std::io::stdout().lock();
+    opts.format.write(&records, &mut out)?;
+    if opts.stats && opts.format == Format::Text {
+        writeln!(out)?;
+        report::write_stats(&records, &mut out)?;
+    }
+    Ok(())
+}
+
+/// The examples in `samples` and the real inputs in `assets`, if it exists.
+/// Examples in `assets` are skipped, as they are copies of the samples.
+fn verify_inputs(samples: &Path, assets: &Path) -> Result<Vec<assets::InputFile>> {
+    let mut inputs = assets::discover(samples)?;
+    if assets.is_dir() {
+        inputs.extend(
+            assets::discover(assets)?
+                .into_iter()
+                .filter(|input| !input.example),
+        );
+        inputs.sort();
+    } else {
+        eprintln!("{} not found, verifying samples only", assets.display());
+    }
+    Ok(inputs)
+}
+
+fn verify(opts: Options) -> Result<()> {
+    let manifest_path = opts
+        .manifest
+        .clone()
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.txt"));
+    let mut manifest = Manifest::load(&manifest_path)?;
+    let dir = opts.assets.clone().unwrap_or_else(assets::default_dir);
+    let inputs = verify_inputs(&assets::samples_dir(), &dir)?;
+    manifest.add_sidecars(&inputs)?;
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    // the entries to append, by file
+    let mut recorded: BTreeMap<PathBuf, String> = BTreeMap::new();
+    for s in select(&opts)? {
+        for input in inputs.iter().filter(|i| i.day == s.day()) {
+            let text = std::fs::read_to_string(&input.path)
+                .with_context(|| format!("could not read {}", input.path.display()))?;
+            let expected = manifest.expected(input.name(), s.part());
+            let status = match (s.solve(&text), expected) {
+                (Ok(answer), Some(e)) if answer.to_string() == e => {
+                    passed += 1;
+                    format!("pass\t{}", answer)
+                }
+                (Ok(answer), Some(e)) => {
+                    failed += 1;
+                    format!("FAIL\texpected {}, got {}", e, answer)
+                }
+                (Ok(answer), None) if opts.record => {
+                    let (path, entry) = if input.example {
+                        (input.sidecar(), format!("{} {}\n", s.part(), answer))
+                    } else {
+                        let entry = format!("{} {} {}\n", input.name(), s.part(), answer);
+                        (manifest_path.clone(), entry)
+                    };
+                    recorded.entry(path).or_default().push_str(&entry);
+                    format!("recorded\t{}", answer)
+                }
+                (Ok(answer), None) => {
+                    missing += 1;
+                    format!("MISSING\t{}", answer)
+                }
+                (Err(err), _) => {
+                    failed += 1;
+                    format!("FAIL\terror: {:#}", err)
+                }
+            };
+            println!(
+                "day{} part{}\t{}\t{}",
+                s.day(),
+                s.part(),
+                input.name(),
+                status
+            );
+        }
+    }
+
+    for (path, entries) in &recorded {
+        append_answers(path, entries)?;
         println!(
-            "{} {}\t{:?}",
-            stringify!($day),
-            stringify!($part),
-            $day::$part::solve(include_str!(concat!("../assets/",stringify!($day),".0.txt")))
+            "recorded {} answer(s) in {}",
+            entries.lines().count(),
+            path.display()
+        );
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        bail!("{} answer(s) did not match", failed);
+    }
+    if missing > 0 {
+        bail!(
+            "{} answer(s) missing, check them and add them with --record",
+            missing
         );
+    }
+    Ok(())
+}
+
+/// Append `entries` to the answers file at `path`, creating a sidecar if
+/// there is none yet.
+fn append_answers(path: &Path, entries: &str) -> Result<()> {
+    let mut text = if path.exists() {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?
+    } else {
+        "# part answer\n".to_string()
+    };
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(entries);
+    std::fs::write(path, text).with_context(|| format!("could not write {}", path.display()))
+}
+
+fn generate(opts: Options) -> Result<()> {
+    let day = match opts.days[..] {
+        [day] => day,
+        [] => bail!("generate needs --day"),
+        _ => bail!("generate takes a single --day"),
+    };
+    let size = match opts.size.or_else(|| generator::default_size(day)) {
+        Some(size) => size,
+        None => bail!("no generator for day {}", day),
+    };
+    print!(
+        "{}",
+        generator::generate(day, opts.seed.unwrap_or_default(), size)?
+    );
+    Ok(())
+}
+
+fn sonar(opts: Options) -> Result<()> {
+    let text = read_input(opts.input.as_deref().unwrap_or("-"))?;
+    // integers when every depth fits in an i128, so they stay exact; the
+    // decimal parser accepts every integer, so its errors point at the
+    // lines that are neither
+    match day1::parse_input::<i128>(&text) {
+        Ok(depths) => sonar_depths(&opts, &depths),
+        Err(_) => sonar_depths(&opts, &day1::parse_input::<f64>(&text)?),
+    }
+}
+
+fn sonar_depths<T: day1::Depth>(opts: &Options, depths: &[T]) -> Result<()> {
+    let window = opts.window.unwrap_or(1);
+    if opts.report {
+        println!("{}", day1::analyze(depths, window)?);
+        return Ok(());
+    }
+    println!(
+        "{}",
+        day1::count_window_increases(depths, window, !opts.non_decreasing)?
+    );
+    Ok(())
+}
+
+fn dive(opts: Options) -> Result<()> {
+    let model = day2::model(opts.model.as_deref().unwrap_or("aimed"))?;
+    let moves = day2::parse_input(&read_input(opts.input.as_deref().unwrap_or("-"))?)?;
+    if opts.trace {
+        let mut out = std::io::stdout().lock();
+        writeln!(out, "step,x,depth,aim")?;
+        for (i, p) in day2::trace(model, moves).enumerate() {
+            let p = p?;
+            writeln!(out, "{},{},{},{}", i + 1, p.x, p.depth, p.aim)?;
+        }
+        return Ok(());
+    }
+    let p = day2::dive(model, moves)?;
+    println!("x {}, depth {}, aim {}", p.x, p.depth, p.aim);
+    println!("{}", p.product()?);
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match parse_args(std::env::args().skip(1))? {
+        Command::Run(opts) => run(opts),
+        Command::Verify(opts) => verify(opts),
+        Command::Generate(opts) => generate(opts),
+        Command::Sonar(opts) => sonar(opts),
+        Command::Dive(opts) => dive(opts),
+        Command::List => {
+            for s in solvers() {
+                println!("day{} part{}\t{}", s.day(), s.part(), s.name());
+            }
+            Ok(())
+        }
+        Command::NewDay(day, name) => {
+            let name = if name.is_empty() {
+                format!("Day {}", day)
+            } else {
+                name
+            };
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+            for path in scaffold::create(root, day, &name)? {
+                println!("wrote {}", path.display());
+            }
+            println!(
+                "fill in samples/day{0}.0.test.txt and its answers, then run cargo fmt",
+                day
+            );
+            Ok(())
+        }
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+    match parse_args(args("run --day 5 --part 2 --input in.txt")).unwrap() {
+        Command::Run(opts) => assert_eq!(
+            opts,
+            Options {
+                days: vec![5],
+                parts: vec![2],
+                input: Some("in.txt".into()),
+                ..Default::default()
+            }
+        ),
+        _ => panic!("expected run"),
+    }
+    assert!(matches!(
+        parse_args(args("")).unwrap(),
+        Command::Run(Options {
+            days,
+            parts,
+            input: None,
+            ..
+        }) if days.is_empty() && parts.is_empty()
+    ));
+    match parse_args(args("-d 1,3 --day 5 -p 2")).unwrap() {
+        Command::Run(opts) => {
+            assert_eq!((opts.days, opts.parts), (vec![1, 3, 5], vec![2]));
+        }
+        _ => panic!("expected run"),
+    }
+    assert!(parse_args(args("-d 1,x")).is_err());
+    match parse_args(args("-f json -s -j 4")).unwrap() {
+        Command::Run(opts) => assert_eq!(
+            (opts.format, opts.stats, opts.jobs),
+            (Format::Json, true, Some(4))
+        ),
+        _ => panic!("expected run"),
+    }
+    assert!(parse_args(args("-f xml")).is_err());
+    match parse_args(args("verify -d 7 --manifest m.txt --record")).unwrap() {
+        Command::Verify(opts) => {
+            assert_eq!(opts.days, vec![7]);
+            assert_eq!(opts.manifest, Some(PathBuf::from("m.txt")));
+            assert!(opts.record);
+        }
+        _ => panic!("expected verify"),
+    }
+    assert!(matches!(parse_args(args("list")).unwrap(), Command::List));
+    match parse_args(args("generate -d 4 --seed 7 --size 3")).unwrap() {
+        Command::Generate(opts) => {
+            assert_eq!(
+                (opts.days, opts.seed, opts.size),
+                (vec![4], Some(7), Some(3))
+            )
+        }
+        _ => panic!("expected generate"),
+    }
+    match parse_args(args("new-day 9 Smoke Basin")).unwrap() {
+        Command::NewDay(day, name) => assert_eq!((day, name.as_str()), (9, "Smoke Basin")),
+        _ => panic!("expected new-day"),
+    }
+    match parse_args(args("sonar -i depths.txt -w 3 --non-decreasing -r")).unwrap() {
+        Command::Sonar(opts) => assert_eq!(
+            (
+                opts.input.as_deref(),
+                opts.window,
+                opts.non_decreasing,
+                opts.report
+            ),
+            (Some("depths.txt"), Some(3), true, true)
+        ),
+        _ => panic!("expected sonar"),
+    }
+    assert!(parse_args(args("sonar --window x")).is_err());
+    match parse_args(args("dive -i course.txt --model direct -t")).unwrap() {
+        Command::Dive(opts) => assert_eq!(
+            (opts.input.as_deref(), opts.model.as_deref(), opts.trace),
+            (Some("course.txt"), Some("direct"), true)
+        ),
+        _ => panic!("expected dive"),
+    }
+    assert!(parse_args(args("new-day")).is_err());
+    assert!(parse_args(args("--input in.txt")).is_err());
+    assert!(parse_args(args("--day x")).is_err());
+}
+
+#[test]
+fn test_select() {
+    let opts = Options {
+        days: vec![1, 3],
+        parts: vec![2],
+        ..Default::default()
     };
+    let selected: Vec<_> = select(&opts)
+        .unwrap()
+        .iter()
+        .map(|s| (s.day(), s.part()))
+        .collect();
+    assert_eq!(selected, vec![(1, 2), (3, 2)]);
+    assert_eq!(select(&Options::default()).unwrap().len(), solvers().len());
 }
 
-fn main() {
-    problems!(
-        day1 Part1 Part2,
-        day2 Part1 Part2,
-        day3 Part1 Part2,
-        day4 Part1 Part2,
-        day5 Part1 Part2,
-        day6 Part1 Part2,
-        day7 Part1 Part2,
-        day8 Part1
+#[test]
+fn test_verify_inputs() {
+    let dir = std::env::temp_dir().join(format!("aoc2021-verify-{}", std::process::id()));
+    let (samples, assets) = (dir.join("samples"), dir.join("assets"));
+    for d in [&samples, &assets] {
+        std::fs::create_dir_all(d).unwrap();
+    }
+    for path in [
+        samples.join("day1.0.test.txt"),
+        assets.join("day1.0.test.txt"),
+        assets.join("day1.0.txt"),
+    ] {
+        std::fs::write(path, "1\n").unwrap();
+    }
+    let inputs = verify_inputs(&samples, &assets);
+    let missing = verify_inputs(&samples, &dir.join("missing"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let names = |inputs: Vec<assets::InputFile>| -> Vec<_> {
+        inputs
+            .iter()
+            .map(|i| i.path.strip_prefix(&dir).unwrap().to_owned())
+            .collect()
+    };
+    assert_eq!(
+        names(inputs.unwrap()),
+        vec![
+            PathBuf::from("assets/day1.0.txt"),
+            PathBuf::from("samples/day1.0.test.txt")
+        ]
+    );
+    assert_eq!(
+        names(missing.unwrap()),
+        vec![PathBuf::from("samples/day1.0.test.txt")]
+    );
+}
+
+#[test]
+fn test_append_answers() {
+    let dir = std::env::temp_dir().join(format!("aoc2021-record-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (sidecar, manifest) = (dir.join("day1.0.test.answers"), dir.join("answers.txt"));
+    std::fs::write(&manifest, "# answers\nday1.0.txt 1 7").unwrap();
+    append_answers(&sidecar, "1 7\n2 5\n").unwrap();
+    append_answers(&manifest, "day1.0.txt 2 5\n").unwrap();
+    let sidecar = std::fs::read_to_string(&sidecar);
+    let manifest = std::fs::read_to_string(&manifest);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let sidecar = Manifest::parse_sidecar("day1.0.test.txt", &sidecar.unwrap()).unwrap();
+    assert_eq!(
+        sidecar.iter().collect::<Vec<_>>(),
+        vec![("day1.0.test.txt", 1, "7"), ("day1.0.test.txt", 2, "5")]
+    );
+    assert_eq!(
+        manifest.unwrap(),
+        "# answers\nday1.0.txt 1 7\nday1.0.txt 2 5\n"
     );
 }
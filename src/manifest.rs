@@ -0,0 +1,131 @@
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::{assets::InputFile, error::ParseError};
+
+/// Expected answers, keyed by input file name and part.
+///
+/// The text format has one `<input> <part> <answer>` entry per line. Blank
+/// lines and anything after a `#` are ignored.
+///
+/// ```text
+/// # input          part  answer
+/// day1.0.test.txt  1     7
+/// ```
+///
+/// An input can also carry its answers in a sidecar file next to it, named
+/// like the input but with an `.answers` extension, with one `<part> <answer>`
+/// entry per line.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    answers: BTreeMap<(String, u8), String>,
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Manifest, ParseError> {
+        Manifest::parse_entries(text, None)
+    }
+
+    /// Parse the sidecar answers of the input file called `input`.
+    pub fn parse_sidecar(input: &str, text: &str) -> Result<Manifest, ParseError> {
+        Manifest::parse_entries(text, Some(input))
+    }
+
+    fn parse_entries(text: &str, input: Option<&str>) -> Result<Manifest, ParseError> {
+        let mut answers = BTreeMap::new();
+        for (i, raw) in text.lines().enumerate() {
+            let line = raw.split('#').next().unwrap_or_default();
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let error = |message: &str| ParseError {
+                line: i + 1,
+                column: 1,
+                snippet: raw.trim_end_matches('\r').to_string(),
+                message: message.into(),
+            };
+            let (input, part, answer) = match (input, &fields[..]) {
+                (_, []) => continue,
+                (None, &[input, part, answer]) => (input, part, answer),
+                (Some(input), &[part, answer]) => (input, part, answer),
+                (None, _) => return Err(error("expected <input> <part> <answer>")),
+                (Some(_), _) => return Err(error("expected <part> <answer>")),
+            };
+            let part = part.parse().map_err(|_| error("invalid part"))?;
+            if answers
+                .insert((input.to_string(), part), answer.to_string())
+                .is_some()
+            {
+                return Err(error("duplicate entry"));
+            }
+        }
+        Ok(Manifest { answers })
+    }
+
+    pub fn load(path: &Path) -> Result<Manifest> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        Manifest::parse(&text).with_context(|| format!("in {}", path.display()))
+    }
+
+    /// Add the sidecar answers of each of `inputs` that has them.
+    pub fn add_sidecars(&mut self, inputs: &[InputFile]) -> Result<()> {
+        for input in inputs {
+            let path = input.sidecar();
+            if !path.exists() {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("could not read {}", path.display()))?;
+            let sidecar = Manifest::parse_sidecar(input.name(), &text)
+                .with_context(|| format!("in {}", path.display()))?;
+            self.answers.extend(sidecar.answers);
+        }
+        Ok(())
+    }
+
+    /// Every `(input, part, answer)` entry, ordered by input then part.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u8, &str)> {
+        self.answers
+            .iter()
+            .map(|((input, part), answer)| (input.as_str(), *part, answer.as_str()))
+    }
+
+    /// The recorded answer for `part` of the input file called `input`.
+    pub fn expected(&self, input: &str, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(input.to_string(), part))
+            .map(String::as_str)
+    }
+}
+
+#[test]
+fn test_manifest() {
+    let m = Manifest::parse(
+        "# input part answer\n\nday1.0.test.txt 1 7\nday1.0.test.txt  2\t5  # windows\n",
+    )
+    .unwrap();
+    assert_eq!(m.expected("day1.0.test.txt", 1), Some("7"));
+    assert_eq!(m.expected("day1.0.test.txt", 2), Some("5"));
+    assert_eq!(m.expected("day1.0.txt", 1), None);
+
+    let e = Manifest::parse("day1.0.txt 1 7\nday1.0.txt x 5\n").unwrap_err();
+    assert_eq!((e.line, e.message.as_str()), (2, "invalid part"));
+    let e = Manifest::parse("day1.0.txt 1\n").unwrap_err();
+    assert_eq!(e.line, 1);
+    let e = Manifest::parse("day1.0.txt 1 7\nday1.0.txt 1 8\n").unwrap_err();
+    assert_eq!((e.line, e.message.as_str()), (2, "duplicate entry"));
+}
+
+#[test]
+fn test_sidecar() {
+    let m = Manifest::parse_sidecar("day1.0.test.txt", "# part answer\n1 7\n2 5\n").unwrap();
+    assert_eq!(
+        m.iter().collect::<Vec<_>>(),
+        vec![("day1.0.test.txt", 1, "7"), ("day1.0.test.txt", 2, "5")]
+    );
+    let e = Manifest::parse_sidecar("day1.0.test.txt", "1 7\nday1.0.test.txt 2 5\n").unwrap_err();
+    assert_eq!(
+        (e.line, e.message.as_str()),
+        (2, "expected <part> <answer>")
+    );
+}
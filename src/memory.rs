@@ -0,0 +1,131 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// A global allocator that forwards to [`System`] and counts the allocations
+/// made by each thread.
+///
+/// Nothing is counted unless a binary installs it:
+///
+/// ```
+/// #[global_allocator]
+/// static ALLOCATOR: aoc2021::memory::CountingAllocator = aoc2021::memory::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+/// The allocations made while running a closure under [`measure`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Allocations {
+    /// Number of allocations, reallocations included.
+    pub count: u64,
+    /// Bytes requested over all allocations.
+    pub bytes: u64,
+    /// The most bytes live at once, over what was live at the start.
+    pub peak: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            count: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    // The counters are gone while the thread shuts down; skip those.
+    let _ = COUNTERS.try_with(|c| {
+        let mut n = c.get();
+        if allocated > 0 {
+            n.count += 1;
+            n.bytes += allocated as u64;
+        }
+        n.live += allocated as i64 - freed as i64;
+        n.peak = n.peak.max(n.live);
+        c.set(n);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size, layout.size());
+        }
+        new
+    }
+}
+
+/// Run `f`, counting the allocations it makes on the current thread.
+///
+/// Always reports zero allocations unless [`CountingAllocator`] is the
+/// global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let start = COUNTERS.with(|c| {
+        let mut n = c.get();
+        n.peak = n.live;
+        c.set(n);
+        n
+    });
+    let out = f();
+    let end = COUNTERS.with(Cell::get);
+    let allocations = Allocations {
+        count: end.count - start.count,
+        bytes: end.bytes - start.bytes,
+        peak: (end.peak - start.live) as u64,
+    };
+    (out, allocations)
+}
+
+#[test]
+fn test_measure() {
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    let ((), allocations) = measure(|| unsafe {
+        let a = CountingAllocator.alloc(layout);
+        let b = CountingAllocator.alloc_zeroed(layout);
+        CountingAllocator.dealloc(a, layout);
+        let b = CountingAllocator.realloc(b, layout, 128);
+        CountingAllocator.dealloc(b, Layout::from_size_align(128, 8).unwrap());
+    });
+    assert_eq!(
+        allocations,
+        Allocations {
+            count: 3,
+            bytes: 256,
+            peak: 128,
+        }
+    );
+}
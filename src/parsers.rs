@@ -0,0 +1,226 @@
+//! Combinators shared by the day parsers.
+//!
+//! They fail with the usual nom error kinds, so [`parse_all`] can describe
+//! what went wrong: [`ErrorKind::Digit`] where a number was expected and
+//! [`ErrorKind::MapRes`] for a number that doesn't fit its type.
+//!
+//! [`parse_all`]: crate::error::parse_all
+//! [`ErrorKind::Digit`]: nom::error::ErrorKind::Digit
+//! [`ErrorKind::MapRes`]: nom::error::ErrorKind::MapRes
+
+use std::{borrow::Cow, io::BufRead, str::FromStr};
+
+use nom::{
+    character::complete::{char, digit1, line_ending, one_of, space0},
+    combinator::{map_res, opt, recognize},
+    multi::separated_list1,
+    sequence::{delimited, pair, terminated, tuple},
+    IResult, Parser,
+};
+
+use crate::error::{parse_line, ParseError};
+
+/// Make inputs saved by different editors look the same to the parsers:
+/// strip a UTF-8 byte order mark, turn CRLF line endings into LF and trim
+/// trailing whitespace and blank lines, leaving a single final newline.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let trimmed = input.trim_end();
+    if trimmed.is_empty() {
+        return Cow::Borrowed("");
+    }
+    let end = trimmed.len();
+    if !trimmed.contains('\r') && input[end..].starts_with('\n') && input.len() == end + 1 {
+        return Cow::Borrowed(input);
+    }
+    let mut out = trimmed.replace("\r\n", "\n");
+    out.push('\n');
+    Cow::Owned(out)
+}
+
+/// The records of a line-oriented input, parsed one line at a time by
+/// `record` as they are read from `reader`, so the input never has to be in
+/// memory all at once.
+///
+/// Lines are read as if [`normalize`]-d first, so a byte order mark, CRLF line
+/// endings and blank lines at the end are fine. Like [`parse_all`], an input
+/// without any records fails with the error `record` gives for an empty line.
+///
+/// [`parse_all`]: crate::error::parse_all
+pub fn records<R, T, F>(reader: R, record: F) -> Records<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> IResult<&str, T>,
+{
+    Records {
+        reader,
+        record,
+        buf: String::new(),
+        number: 0,
+        blank: None,
+        empty: true,
+    }
+}
+
+/// The iterator returned by [`records`].
+pub struct Records<R, F> {
+    reader: R,
+    record: F,
+    buf: String,
+    /// The number of the line in `buf`.
+    number: usize,
+    /// The first of the blank lines just read, if any.
+    blank: Option<usize>,
+    /// Whether no records have been read yet.
+    empty: bool,
+}
+
+impl<R, T, F> Iterator for Records<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> IResult<&str, T>,
+{
+    type Item = anyhow::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buf.clear();
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) if self.empty => {
+                    self.empty = false;
+                    return Some(parse_line(&mut self.record, "", 1).map_err(Into::into));
+                }
+                Ok(0) => return None,
+                Ok(_) => self.number += 1,
+                Err(e) => return Some(Err(e.into())),
+            }
+            let mut line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+            line = line.strip_suffix('\r').unwrap_or(line);
+            if self.number == 1 {
+                line = line.strip_prefix('\u{feff}').unwrap_or(line);
+            }
+            if line.trim().is_empty() {
+                self.blank.get_or_insert(self.number);
+                continue;
+            }
+            if let Some(blank) = self.blank {
+                return Some(Err(ParseError {
+                    line: blank,
+                    column: 1,
+                    snippet: String::new(),
+                    message: "blank line".into(),
+                }
+                .into()));
+            }
+            self.empty = false;
+            return Some(parse_line(&mut self.record, line, self.number).map_err(Into::into));
+        }
+    }
+}
+
+/// An unsigned integer of any type, e.g. `42`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// An integer of any type with an optional sign, e.g. `-42` or `+42`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// A number of any type with an optional sign and fraction, e.g. `-4.25`.
+pub fn decimal<T: FromStr>(input: &str) -> IResult<&str, T> {
+    let number = tuple((opt(one_of("+-")), digit1, opt(pair(char('.'), digit1))));
+    map_res(recognize(number), str::parse)(input)
+}
+
+/// One or more `item`s separated by `sep`, e.g. `1,2,3`.
+pub fn list<'a, O, O2, S, F>(sep: S, item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    S: Parser<&'a str, O2, nom::error::Error<&'a str>>,
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(sep, item)
+}
+
+/// One `record` per line, with an optional final line ending.
+pub fn lines<'a, O, F>(record: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    terminated(separated_list1(line_ending, record), opt(line_ending))
+}
+
+/// `inner` surrounded by optional spaces or tabs, but not line endings.
+pub fn ws<'a, O, F>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    delimited(space0, inner, space0)
+}
+
+#[test]
+fn test_normalize() {
+    assert!(matches!(normalize("1\n2\n"), Cow::Borrowed("1\n2\n")));
+    assert_eq!(normalize("\u{feff}1\r\n2\r\n\r\n \n"), "1\n2\n");
+    assert_eq!(normalize("1\n2"), "1\n2\n");
+    assert_eq!(normalize("1\n\n2\t\n\n"), "1\n\n2\n");
+    assert_eq!(normalize("\u{feff}\r\n"), "");
+}
+
+#[test]
+fn test_records() {
+    let read =
+        |input: &str| records(input.as_bytes(), signed::<i32>).collect::<anyhow::Result<Vec<_>>>();
+    assert_eq!(read("1\n-2\n").unwrap(), vec![1, -2]);
+    assert_eq!(read("\u{feff}1\r\n-2\r\n\r\n  \n").unwrap(), vec![1, -2]);
+
+    let error = |input: &str| {
+        let e = read(input).unwrap_err().downcast::<ParseError>().unwrap();
+        (e.line, e.column, e.message)
+    };
+    assert_eq!(error("1\n2\n3x\n"), (3, 2, "unexpected input".into()));
+    assert_eq!(error("1\n\n2\n"), (2, 1, "blank line".into()));
+    assert_eq!(error("1\nx\n"), (2, 1, "expected a number".into()));
+    assert_eq!(error(""), (1, 1, "expected a number".into()));
+    assert_eq!(error("\r\n\n"), (1, 1, "expected a number".into()));
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(unsigned::<u8>("255,"), Ok((",", 255)));
+    assert!(unsigned::<u8>("256").is_err());
+    assert!(unsigned::<u8>("-1").is_err());
+    assert_eq!(signed::<i16>("-12x"), Ok(("x", -12)));
+    assert_eq!(signed::<i16>("+12"), Ok(("", 12)));
+    assert!(signed::<i16>("-").is_err());
+    assert_eq!(
+        signed::<i128>("-170141183460469231731687303715884105728").map(|(_, n)| n),
+        Ok(i128::MIN)
+    );
+    assert_eq!(decimal::<f64>("-4.25,"), Ok((",", -4.25)));
+    assert_eq!(decimal::<f64>("+7"), Ok(("", 7.0)));
+    assert_eq!(decimal::<f64>("7."), Ok((".", 7.0)));
+    assert!(decimal::<f64>(".5").is_err());
+    assert!(decimal::<i32>("1.5").is_err());
+}
+
+#[test]
+fn test_combinators() {
+    use nom::{character::complete::char, multi::many1};
+
+    assert_eq!(
+        list(char(','), unsigned::<u32>)("1,2,3\n"),
+        Ok(("\n", vec![1, 2, 3]))
+    );
+    assert_eq!(
+        list(char(','), unsigned::<u32>)("1,2,"),
+        Ok((",", vec![1, 2]))
+    );
+    assert_eq!(lines(signed::<i32>)("1\r\n-2\n"), Ok(("", vec![1, -2])));
+    assert_eq!(lines(signed::<i32>)("1\n\n2"), Ok(("\n2", vec![1])));
+    assert_eq!(
+        lines(many1(ws(unsigned::<u8>)))("1  2 \n 3"),
+        Ok(("", vec![vec![1, 2], vec![3]]))
+    );
+}
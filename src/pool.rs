@@ -0,0 +1,60 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// The number of threads to use for `jobs`, where 0 means one per CPU.
+pub fn threads(jobs: usize) -> usize {
+    match jobs {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+}
+
+/// Apply `f` to each of `items` on up to `threads` threads.
+///
+/// Threads take the next unclaimed item until none are left, so slow items
+/// don't hold up the rest. The results are in the order of `items`.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = threads.clamp(1, items.len().max(1));
+    if threads == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else { break };
+                let result = f(item);
+                results.lock().unwrap().push((i, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+#[test]
+fn test_map() {
+    let items: Vec<u64> = (0..100).collect();
+    let slow_first = |&n: &u64| {
+        thread::sleep(std::time::Duration::from_micros(100 - n));
+        n * n
+    };
+    let expected: Vec<_> = items.iter().map(|n| n * n).collect();
+    assert_eq!(map(&items, 1, slow_first), expected);
+    assert_eq!(map(&items, 8, slow_first), expected);
+    assert_eq!(map(&[] as &[u64], 8, slow_first), vec![]);
+}
@@ -0,0 +1,301 @@
+//! Straightforward, slow solutions to check the real ones against.
+//!
+//! These follow the puzzle text as literally as possible: window sums are
+//! summed, fish are simulated one by one for as long as that is feasible,
+//! and crabs try every position.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::{
+    answer::Answer,
+    day1, day2,
+    day2::{Dir, Move},
+    day3, day4, day5, day6, day7, day8,
+};
+
+/// Solve `part` of `day` the slow way.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Answer> {
+    match reference(day, part) {
+        Some(solve) => solve(input),
+        None => bail!("no reference solution for day{} part{}", day, part),
+    }
+}
+
+/// The slow solution to `part` of `day`, if there is one.
+pub fn reference(day: u8, part: u8) -> Option<fn(&str) -> Result<Answer>> {
+    Some(match (day, part) {
+        (1, 1) => |input| Ok(count_increases(&day1::parse_input::<i64>(input)?).into()),
+        (1, 2) => |input| {
+            let xs = day1::parse_input::<i64>(input)?;
+            let sums: Vec<_> = xs.windows(3).map(|w| w.iter().sum()).collect();
+            Ok(count_increases(&sums).into())
+        },
+        (2, 1) => |input| Ok(dive(&day2::parse_input(input)?).into()),
+        (2, 2) => |input| Ok(dive_with_aim(&day2::parse_input(input)?).into()),
+        (3, 1) => |input| Ok(power(&day3::parse_input(input)?).into()),
+        (3, 2) => |input| Ok(life_support(&day3::parse_input(input)?).into()),
+        (4, 1) => |input| {
+            let scores = bingo_scores(&day4::parse_input(input)?);
+            Ok(scores
+                .first()
+                .copied()
+                .ok_or_else(|| anyhow!("no winner"))?
+                .into())
+        },
+        (4, 2) => |input| {
+            let scores = bingo_scores(&day4::parse_input(input)?);
+            Ok(scores
+                .last()
+                .copied()
+                .ok_or_else(|| anyhow!("no winner"))?
+                .into())
+        },
+        (5, 1) => |input| Ok(overlaps(&day5::parse_input(input)?, false).into()),
+        (5, 2) => |input| Ok(overlaps(&day5::parse_input(input)?, true).into()),
+        (6, 1) => |input| Ok(fish_simulated(&day6::parse_input(input)?, 80).into()),
+        (6, 2) => |input| Ok(fish_counted(&day6::parse_input(input)?, 256).into()),
+        (7, 1) => |input| Ok(fuel(&day7::parse_input(input)?, |d| d).into()),
+        (7, 2) => |input| Ok(fuel(&day7::parse_input(input)?, |d| d * (d + 1) / 2).into()),
+        (8, 1) => |input| Ok(easy_digits(&day8::parse_input(input)?).into()),
+        _ => return None,
+    })
+}
+
+fn count_increases(xs: &[i64]) -> u64 {
+    (1..xs.len()).filter(|&i| xs[i] > xs[i - 1]).count() as u64
+}
+
+fn dive(moves: &[Move]) -> i64 {
+    let (mut x, mut depth) = (0, 0);
+    for m in moves {
+        match m.dir {
+            Dir::F => x += m.amt,
+            Dir::U => depth -= m.amt,
+            Dir::D => depth += m.amt,
+        }
+    }
+    x * depth
+}
+
+fn dive_with_aim(moves: &[Move]) -> i64 {
+    let (mut x, mut depth, mut aim) = (0, 0, 0);
+    for m in moves {
+        match m.dir {
+            Dir::F => {
+                x += m.amt;
+                depth += aim * m.amt;
+            }
+            Dir::U => aim -= m.amt,
+            Dir::D => aim += m.amt,
+        }
+    }
+    x * depth
+}
+
+fn to_number(bits: &[u8]) -> u64 {
+    bits.iter().fold(0, |n, &b| n * 2 + b as u64)
+}
+
+fn power(report: &[Vec<u8>]) -> u64 {
+    let width = report.iter().map(Vec::len).max().unwrap_or(0);
+    let mut gamma = Vec::new();
+    let mut epsilon = Vec::new();
+    for i in 0..width {
+        let ones = report.iter().filter(|n| n.get(i) == Some(&1)).count();
+        let zeros = report.iter().filter(|n| n.get(i) == Some(&0)).count();
+        gamma.push((ones > zeros) as u8);
+        epsilon.push((ones <= zeros) as u8);
+    }
+    to_number(&gamma) * to_number(&epsilon)
+}
+
+/// Filter the report bit by bit, keeping the numbers whose bit is the one
+/// `keep` picks given the counts of zeros and ones, until one is left.
+fn rating(report: &[Vec<u8>], keep: fn(usize, usize) -> u8) -> u64 {
+    let mut left: Vec<_> = report.iter().collect();
+    let mut i = 0;
+    while left.len() > 1 {
+        let ones = left.iter().filter(|n| n[i] == 1).count();
+        let bit = keep(left.len() - ones, ones);
+        left.retain(|n| n[i] == bit);
+        i += 1;
+    }
+    left.first().map_or(0, |n| to_number(n))
+}
+
+fn life_support(report: &[Vec<u8>]) -> u64 {
+    let oxygen = rating(report, |zeros, ones| (ones >= zeros) as u8);
+    // the least common bit of those still present
+    let co2 = rating(report, |zeros, ones| {
+        (zeros == 0 || (ones > 0 && ones < zeros)) as u8
+    });
+    oxygen * co2
+}
+
+/// The score of each board when it wins, in the order they win.
+fn bingo_scores(input: &day4::Input) -> Vec<u64> {
+    let mut marked = vec![[[false; 5]; 5]; input.boards.len()];
+    let mut won = vec![false; input.boards.len()];
+    let mut scores = Vec::new();
+    for &n in &input.numbers {
+        for (b, board) in input.boards.iter().enumerate() {
+            if won[b] {
+                continue;
+            }
+            let m = &mut marked[b];
+            for (row, marks) in board.0.iter().zip(m.iter_mut()) {
+                for (&x, mark) in row.iter().zip(marks.iter_mut()) {
+                    *mark |= x == n;
+                }
+            }
+            let row = (0..5).any(|r| (0..5).all(|c| m[r][c]));
+            let column = (0..5).any(|c| (0..5).all(|r| m[r][c]));
+            if row || column {
+                won[b] = true;
+                let unmarked: u64 = board
+                    .0
+                    .iter()
+                    .flatten()
+                    .zip(m.iter().flatten())
+                    .filter(|(_, &mark)| !mark)
+                    .map(|(&x, _)| x as u64)
+                    .sum();
+                scores.push(unmarked * n as u64);
+            }
+        }
+    }
+    scores
+}
+
+fn overlaps(lines: &[day5::Line], diagonals: bool) -> u64 {
+    let mut covered: HashMap<(i32, i32), u32> = HashMap::new();
+    for line in lines {
+        let (day5::Point((x1, y1)), day5::Point((x2, y2))) = line.0;
+        if x1 == x2 {
+            for y in y1.min(y2)..=y1.max(y2) {
+                *covered.entry((x1, y)).or_default() += 1;
+            }
+        } else if y1 == y2 {
+            for x in x1.min(x2)..=x1.max(x2) {
+                *covered.entry((x, y1)).or_default() += 1;
+            }
+        } else if diagonals {
+            let (dx, dy) = ((x2 - x1).signum(), (y2 - y1).signum());
+            let (mut x, mut y) = (x1, y1);
+            loop {
+                *covered.entry((x, y)).or_default() += 1;
+                if x == x2 {
+                    break;
+                }
+                x += dx;
+                y += dy;
+            }
+        }
+    }
+    covered.values().filter(|&&n| n > 1).count() as u64
+}
+
+/// Simulate every fish, one day at a time.
+fn fish_simulated(fish: &[u8], days: usize) -> u64 {
+    let mut fish = fish.to_vec();
+    for _ in 0..days {
+        let mut born = 0;
+        for f in fish.iter_mut() {
+            if *f == 0 {
+                *f = 6;
+                born += 1;
+            } else {
+                *f -= 1;
+            }
+        }
+        fish.extend(std::iter::repeat_n(8, born));
+    }
+    fish.len() as u64
+}
+
+/// Count each fish and its descendants, too many to simulate one by one.
+fn fish_counted(fish: &[u8], days: u64) -> u64 {
+    fn family(timer: u64, days: u64, memo: &mut HashMap<(u64, u64), u64>) -> u64 {
+        if days <= timer {
+            return 1;
+        }
+        if let Some(&n) = memo.get(&(timer, days)) {
+            return n;
+        }
+        // the fish restarts at 6 and its child starts at 8 on day timer + 1
+        let left = days - timer - 1;
+        let n = family(6, left, memo) + family(8, left, memo);
+        memo.insert((timer, days), n);
+        n
+    }
+    let mut memo = HashMap::new();
+    fish.iter()
+        .map(|&f| family(f as u64, days, &mut memo))
+        .sum()
+}
+
+/// The least fuel to line up all crabs at any one position.
+fn fuel(crabs: &[u16], cost: fn(u64) -> u64) -> u64 {
+    let max = crabs.iter().copied().max().unwrap_or(0);
+    (0..=max)
+        .map(|p| {
+            crabs
+                .iter()
+                .map(|&c| cost((c as i64 - p as i64).unsigned_abs()))
+                .sum()
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+fn easy_digits(notes: &[day8::Note]) -> u64 {
+    notes
+        .iter()
+        .flat_map(|n| &n.outputs)
+        .filter(|o| [2, 3, 4, 7].contains(&o.count_ones()))
+        .count() as u64
+}
+
+/// The solvers that have a reference solution to check against.
+#[cfg(test)]
+fn checked() -> impl Iterator<Item = &'static &'static dyn crate::Solver> {
+    crate::solvers()
+        .iter()
+        .filter(|s| reference(s.day(), s.part()).is_some())
+}
+
+#[test]
+fn test_samples() {
+    for s in checked() {
+        let path = crate::assets::samples_dir().join(format!("day{}.0.test.txt", s.day()));
+        let input = std::fs::read_to_string(path).unwrap();
+        assert_eq!(
+            solve(s.day(), s.part(), &input).unwrap(),
+            s.solve(&input).unwrap(),
+            "day{} part{}",
+            s.day(),
+            s.part()
+        );
+    }
+}
+
+#[test]
+fn test_differential() {
+    let generated = checked().filter(|s| crate::generator::default_size(s.day()).is_some());
+    for s in generated {
+        for seed in 0..20 {
+            let input = crate::generator::generate(s.day(), seed, 20).unwrap();
+            assert_eq!(
+                solve(s.day(), s.part(), &input).unwrap(),
+                s.solve(&input).unwrap(),
+                "day{} part{} seed {}:\n{}",
+                s.day(),
+                s.part(),
+                seed,
+                input
+            );
+        }
+    }
+}
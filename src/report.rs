@@ -0,0 +1,392 @@
+use std::{
+    fmt::Write as _,
+    io,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Error};
+
+use crate::{
+    answer::Answer,
+    memory::{self, Allocations},
+    solver::Solver,
+};
+
+/// The outcome of running one solver on one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    /// The name of the input.
+    pub input: String,
+    /// The answer, or the rendered error.
+    pub answer: Result<Answer, String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Allocations made while parsing and solving.
+    pub allocations: Allocations,
+}
+
+impl Record {
+    /// Parse and solve `text`, the input called `input`, timing each phase.
+    pub fn run(solver: &dyn Solver, input: &str, text: &str) -> Record {
+        let mut parse_time = Duration::ZERO;
+        let mut solve_time = Duration::ZERO;
+        let (answer, allocations) = memory::measure(|| {
+            let start = Instant::now();
+            let parsed = solver.parse(text);
+            parse_time = start.elapsed();
+            let start = Instant::now();
+            let answer = parsed.and_then(|p| solver.solve_parsed(&*p));
+            solve_time = start.elapsed();
+            answer
+        });
+        Record {
+            day: solver.day(),
+            part: solver.part(),
+            input: input.to_string(),
+            answer: answer.map_err(|e| format!("{:#}", e)),
+            parse_time,
+            solve_time,
+            allocations,
+        }
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+}
+
+/// How to write a list of [`Record`]s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A table per day with one column per input.
+    #[default]
+    Text,
+    /// An array of objects.
+    Json,
+    /// A header row followed by a row per record.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("unknown format {:?}, expected text, json or csv", s),
+        }
+    }
+}
+
+impl Format {
+    pub fn write(self, records: &[Record], out: &mut impl io::Write) -> io::Result<()> {
+        match self {
+            Format::Text => write_text(records, out),
+            Format::Json => write_json(records, out),
+            Format::Csv => write_csv(records, out),
+        }
+    }
+}
+
+/// Render a duration with a unit that keeps it readable, e.g. `12.3µs`.
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{}ns", ns)
+    } else if ns < 1_000_000 {
+        format!("{:.1}µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.1}ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.2}s", d.as_secs_f64())
+    }
+}
+
+/// Render a byte count with a binary unit, e.g. `1.5KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut n = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while n >= 1024.0 && unit + 1 < UNITS.len() {
+        n /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", n, UNITS[unit])
+}
+
+/// Print `rows` with their columns aligned.
+fn write_table(rows: &[Vec<String>], out: &mut impl io::Write) -> io::Result<()> {
+    let mut widths = Vec::new();
+    for row in rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    for row in rows {
+        let line: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{:<w$}", cell, w = w))
+            .collect();
+        writeln!(out, "{}", line.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+/// Keep the first occurrence of each item.
+fn unique<T: PartialEq>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut out = Vec::new();
+    for item in items {
+        if !out.contains(&item) {
+            out.push(item);
+        }
+    }
+    out
+}
+
+fn write_text(records: &[Record], out: &mut impl io::Write) -> io::Result<()> {
+    for day in unique(records.iter().map(|r| r.day)) {
+        let records: Vec<_> = records.iter().filter(|r| r.day == day).collect();
+        let inputs = unique(records.iter().map(|r| r.input.as_str()));
+
+        let mut rows = vec![std::iter::once(format!("day{}", day))
+            .chain(inputs.iter().map(|i| i.to_string()))
+            .collect::<Vec<_>>()];
+        let mut errors = Vec::new();
+        for part in unique(records.iter().map(|r| r.part)) {
+            let mut row = vec![format!("  part{}", part)];
+            for input in &inputs {
+                let cell = match records.iter().find(|r| r.part == part && r.input == *input) {
+                    Some(r) => match &r.answer {
+                        Ok(answer) => format!("{} ({})", answer, format_duration(r.total_time())),
+                        Err(e) => {
+                            errors.push(format!("day{} part{} {}: {}", day, part, input, e));
+                            "error".to_string()
+                        }
+                    },
+                    None => String::new(),
+                };
+                row.push(cell);
+            }
+            rows.push(row);
+        }
+        write_table(&rows, out)?;
+        for e in errors {
+            writeln!(out, "{}", e)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a table of the time and memory each record took, one row per record.
+pub fn write_stats(records: &[Record], out: &mut impl io::Write) -> io::Result<()> {
+    let header = [
+        "day", "part", "input", "parse", "solve", "total", "allocs", "bytes", "peak",
+    ];
+    let mut rows = vec![header.iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    for r in records {
+        rows.push(vec![
+            format!("day{}", r.day),
+            format!("part{}", r.part),
+            r.input.clone(),
+            format_duration(r.parse_time),
+            format_duration(r.solve_time),
+            format_duration(r.total_time()),
+            r.allocations.count.to_string(),
+            format_bytes(r.allocations.bytes),
+            format_bytes(r.allocations.peak),
+        ]);
+    }
+    write_table(&rows, out)
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn write_json(records: &[Record], out: &mut impl io::Write) -> io::Result<()> {
+    writeln!(out, "[")?;
+    for (i, r) in records.iter().enumerate() {
+        let answer = match &r.answer {
+            Ok(Answer::Text(s)) => format!("\"answer\": {}", json_string(s)),
+            Ok(answer) => format!("\"answer\": {}", answer),
+            Err(e) => format!("\"error\": {}", json_string(e)),
+        };
+        let sep = if i + 1 < records.len() { "," } else { "" };
+        writeln!(
+            out,
+            "  {{\"day\": {}, \"part\": {}, \"input\": {}, {}, \"parse_ns\": {}, \"solve_ns\": {}, \"time_ns\": {}, \"allocs\": {}, \"alloc_bytes\": {}, \"peak_bytes\": {}}}{}",
+            r.day,
+            r.part,
+            json_string(&r.input),
+            answer,
+            r.parse_time.as_nanos(),
+            r.solve_time.as_nanos(),
+            r.total_time().as_nanos(),
+            r.allocations.count,
+            r.allocations.bytes,
+            r.allocations.peak,
+            sep
+        )?;
+    }
+    writeln!(out, "]")
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn write_csv(records: &[Record], out: &mut impl io::Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "day,part,input,answer,error,parse_ns,solve_ns,time_ns,allocs,alloc_bytes,peak_bytes"
+    )?;
+    for r in records {
+        let (answer, error) = match &r.answer {
+            Ok(answer) => (answer.to_string(), String::new()),
+            Err(e) => (String::new(), e.clone()),
+        };
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            r.day,
+            r.part,
+            csv_field(&r.input),
+            csv_field(&answer),
+            csv_field(&error),
+            r.parse_time.as_nanos(),
+            r.solve_time.as_nanos(),
+            r.total_time().as_nanos(),
+            r.allocations.count,
+            r.allocations.bytes,
+            r.allocations.peak
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+fn sample() -> Vec<Record> {
+    let record = |part, input: &str, answer| Record {
+        day: 1,
+        part,
+        input: input.into(),
+        answer,
+        parse_time: Duration::from_micros(2),
+        solve_time: Duration::from_micros(10),
+        allocations: Allocations {
+            count: 3,
+            bytes: 2048,
+            peak: 1536,
+        },
+    };
+    vec![
+        record(1, "day1.0.txt", Ok(Answer::Int(7))),
+        record(1, "day1.1.txt", Err("line 1, column 1: \"x\"".into())),
+        record(2, "day1.0.txt", Ok(Answer::Text("A,B".into()))),
+    ]
+}
+
+#[test]
+fn test_text() {
+    let mut out = Vec::new();
+    Format::Text.write(&sample(), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\
+day1     day1.0.txt    day1.1.txt
+  part1  7 (12.0µs)    error
+  part2  A,B (12.0µs)
+day1 part1 day1.1.txt: line 1, column 1: \"x\"
+"
+    );
+}
+
+#[test]
+fn test_json() {
+    let mut out = Vec::new();
+    Format::Json.write(&sample(), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        r#"[
+  {"day": 1, "part": 1, "input": "day1.0.txt", "answer": 7, "parse_ns": 2000, "solve_ns": 10000, "time_ns": 12000, "allocs": 3, "alloc_bytes": 2048, "peak_bytes": 1536},
+  {"day": 1, "part": 1, "input": "day1.1.txt", "error": "line 1, column 1: \"x\"", "parse_ns": 2000, "solve_ns": 10000, "time_ns": 12000, "allocs": 3, "alloc_bytes": 2048, "peak_bytes": 1536},
+  {"day": 1, "part": 2, "input": "day1.0.txt", "answer": "A,B", "parse_ns": 2000, "solve_ns": 10000, "time_ns": 12000, "allocs": 3, "alloc_bytes": 2048, "peak_bytes": 1536}
+]
+"#
+    );
+}
+
+#[test]
+fn test_csv() {
+    let mut out = Vec::new();
+    Format::Csv.write(&sample(), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        r#"day,part,input,answer,error,parse_ns,solve_ns,time_ns,allocs,alloc_bytes,peak_bytes
+1,1,day1.0.txt,7,,2000,10000,12000,3,2048,1536
+1,1,day1.1.txt,,"line 1, column 1: ""x""",2000,10000,12000,3,2048,1536
+1,2,day1.0.txt,"A,B",,2000,10000,12000,3,2048,1536
+"#
+    );
+}
+
+#[test]
+fn test_stats() {
+    let mut out = Vec::new();
+    write_stats(&sample()[..1], &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\
+day   part   input       parse  solve   total   allocs  bytes   peak
+day1  part1  day1.0.txt  2.0µs  10.0µs  12.0µs  3       2.0KiB  1.5KiB
+"
+    );
+}
+
+#[test]
+fn test_run() {
+    let r = Record::run(&crate::day1::Part1, "x", "199\n200\n");
+    assert_eq!((r.day, r.part, r.answer), (1, 1, Ok(Answer::Int(1))));
+    let r = Record::run(&crate::day1::Part1, "x", "nope");
+    assert!(r.answer.is_err());
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+    assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3µs");
+    assert_eq!(format_duration(Duration::from_micros(4_560)), "4.6ms");
+    assert_eq!(format_duration(Duration::from_millis(1_234)), "1.23s");
+    assert_eq!(format_bytes(1023), "1023B");
+    assert_eq!(format_bytes(3 << 20), "3.0MiB");
+}
@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+const MODULE: &str = r#"use anyhow::{bail, Result};
+use nom::{bytes::complete::is_not, combinator::map, IResult};
+
+use crate::{error::parse_all, parsers::lines, solver::Puzzle};
+
+pub struct Part1;
+pub struct Part2;
+
+/// One record per line.
+pub fn parse(input: &str) -> IResult<&str, Vec<String>> {
+    lines(map(is_not("\r\n"), String::from))(input)
+}
+
+/// Parse a whole puzzle input, failing with a
+/// [`ParseError`](crate::error::ParseError) on malformed input.
+pub fn parse_input(input: &str) -> Result<Vec<String>> {
+    Ok(parse_all(parse, input)?)
+}
+
+impl Puzzle for Part1 {
+    const DAY: u8 = {DAY};
+    const PART: u8 = 1;
+    const NAME: &'static str = "{NAME}";
+
+    type Input = Vec<String>;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(_lines: &Vec<String>) -> Result<i64> {
+        bail!("day{DAY} part1 is not implemented")
+    }
+}
+
+impl Puzzle for Part2 {
+    const DAY: u8 = {DAY};
+    const PART: u8 = 2;
+    const NAME: &'static str = "{NAME}";
+
+    type Input = Vec<String>;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        parse_input(input)
+    }
+
+    fn solve_parsed(_lines: &Vec<String>) -> Result<i64> {
+        bail!("day{DAY} part2 is not implemented")
+    }
+}
+
+#[test]
+fn day{DAY}() {
+    assert_eq!(
+        0,
+        Part1::solve(include_str!("../samples/day{DAY}.0.test.txt")).unwrap()
+    );
+    assert_eq!(
+        0,
+        Part2::solve(include_str!("../samples/day{DAY}.0.test.txt")).unwrap()
+    );
+}
+"#;
+
+/// The source of a new `dayN` module whose parts are not implemented yet,
+/// with an example test that fails until they are.
+pub fn module(day: u8, name: &str) -> String {
+    MODULE
+        .replace("{DAY}", &day.to_string())
+        .replace("{NAME}", &name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The day number of a `dayN` identifier.
+fn day_of(ident: &str) -> Option<u8> {
+    ident.strip_prefix("day")?.parse().ok()
+}
+
+/// Add `pub mod dayN;` to `lib`, the source of `lib.rs`, among the other days.
+fn register_module(lib: &str, day: u8) -> Result<String> {
+    let mut lines: Vec<_> = lib.lines().collect();
+    let days: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| {
+            let ident = l.strip_prefix("pub mod ")?.strip_suffix(';')?;
+            Some((i, day_of(ident)?))
+        })
+        .collect();
+    if days.iter().any(|&(_, d)| d == day) {
+        bail!("day{} is already declared in lib.rs", day);
+    }
+    let at = match days.iter().rev().find(|&&(_, d)| d < day) {
+        Some(&(i, _)) => i + 1,
+        None => match days.first() {
+            Some(&(i, _)) => i,
+            None => bail!("no day modules found in lib.rs"),
+        },
+    };
+    let decl = format!("pub mod day{};", day);
+    lines.insert(at, &decl);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Import `dayN` in `solvers`, the source of `solver.rs`, and add its parts to
+/// the registry in day order.
+fn register_solvers(solvers: &str, day: u8) -> Result<String> {
+    let start = solvers
+        .find("use crate::{")
+        .context("no crate imports in solver.rs")?;
+    let end = start + solvers[start..].find("};").context("unterminated import")? + 2;
+    let mut items: Vec<String> = solvers[start + "use crate::{".len()..end - 2]
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if items.iter().any(|i| day_of(i) == Some(day)) {
+        bail!("day{} is already registered in solver.rs", day);
+    }
+    items.push(format!("day{}", day));
+    items.sort_by_key(|i| (day_of(i).is_some(), day_of(i)));
+    let imports = format!("use crate::{{{}}};", items.join(", "));
+
+    let list = solvers
+        .find("static SOLVERS")
+        .context("no SOLVERS list in solver.rs")?;
+    let list_end = list + solvers[list..].find("];").context("unterminated SOLVERS")?;
+    let at = solvers[list..list_end]
+        .match_indices("    &day")
+        .find(|(i, _)| {
+            let ident = solvers[list + i + 5..]
+                .split("::")
+                .next()
+                .unwrap_or_default();
+            day_of(ident).is_some_and(|d| d > day)
+        })
+        .map_or(list_end, |(i, _)| list + i);
+    let entries = format!("    &day{0}::Part1,\n    &day{0}::Part2,\n", day);
+
+    Ok([
+        &solvers[..start],
+        &imports,
+        &solvers[end..at],
+        &entries,
+        &solvers[at..],
+    ]
+    .concat())
+}
+
+/// Create the module, the example input and its answers for `day` under
+/// `root`, the crate directory, and register the new solvers.
+///
+/// Returns the files written.
+pub fn create(root: &Path, day: u8, name: &str) -> Result<Vec<PathBuf>> {
+    let module_path = root.join(format!("src/day{}.rs", day));
+    if module_path.exists() {
+        bail!("{} already exists", module_path.display());
+    }
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))
+    };
+    let lib_path = root.join("src/lib.rs");
+    let solver_path = root.join("src/solver.rs");
+    let lib = register_module(&read(&lib_path)?, day)?;
+    let solvers = register_solvers(&read(&solver_path)?, day)?;
+
+    let samples = root.join("samples");
+    let files = [
+        (module_path, module(day, name)),
+        (
+            samples.join(format!("day{}.0.test.txt", day)),
+            String::new(),
+        ),
+        (
+            samples.join(format!("day{}.0.test.answers", day)),
+            // placeholders, so the new day's example cases run and fail
+            "# part answer\n1 0\n2 0\n".to_string(),
+        ),
+        (lib_path, lib),
+        (solver_path, solvers),
+    ];
+    for (path, text) in &files {
+        std::fs::write(path, text)
+            .with_context(|| format!("could not write {}", path.display()))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[test]
+fn test_register() {
+    let lib = "pub mod answer;\npub mod day1;\npub mod day3;\npub mod error;\n";
+    assert_eq!(
+        register_module(lib, 2).unwrap(),
+        "pub mod answer;\npub mod day1;\npub mod day2;\npub mod day3;\npub mod error;\n"
+    );
+    assert_eq!(
+        register_module(lib, 4).unwrap(),
+        "pub mod answer;\npub mod day1;\npub mod day3;\npub mod day4;\npub mod error;\n"
+    );
+    assert!(register_module(lib, 3).is_err());
+
+    let solvers = "\
+use crate::{answer::Answer, day1, day3};
+
+static SOLVERS: &[&dyn Solver] = &[
+    &day1::Part1,
+    &day3::Part1,
+];
+";
+    assert_eq!(
+        register_solvers(solvers, 2).unwrap(),
+        "\
+use crate::{answer::Answer, day1, day2, day3};
+
+static SOLVERS: &[&dyn Solver] = &[
+    &day1::Part1,
+    &day2::Part1,
+    &day2::Part2,
+    &day3::Part1,
+];
+"
+    );
+    assert!(register_solvers(&register_solvers(solvers, 12).unwrap(), 12).is_err());
+}
+
+#[test]
+fn test_module() {
+    let source = module(9, "Smoke \"Basin\"");
+    assert!(source.contains("const DAY: u8 = 9;"));
+    assert!(source.contains(r#"const NAME: &'static str = "Smoke \"Basin\"";"#));
+    assert!(source.contains("fn day9()"));
+    assert!(source.contains(r#"include_str!("../samples/day9.0.test.txt")"#));
+}
+
+#[test]
+fn test_create() {
+    use crate::manifest::Manifest;
+
+    let root = std::env::temp_dir().join(format!("aoc2021-scaffold-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::create_dir_all(root.join("samples")).unwrap();
+    std::fs::write(root.join("src/lib.rs"), "pub mod day1;\n").unwrap();
+    std::fs::write(
+        root.join("src/solver.rs"),
+        "use crate::{day1};\n\nstatic SOLVERS: &[&dyn Solver] = &[\n    &day1::Part1,\n];\n",
+    )
+    .unwrap();
+    let written = create(&root, 9, "Smoke Basin");
+    let answers = std::fs::read_to_string(root.join("samples/day9.0.test.answers"));
+    let again = create(&root, 9, "Smoke Basin");
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(written.unwrap().len(), 5);
+    let answers = Manifest::parse_sidecar("day9.0.test.txt", &answers.unwrap()).unwrap();
+    assert_eq!(
+        answers.iter().collect::<Vec<_>>(),
+        vec![("day9.0.test.txt", 1, "0"), ("day9.0.test.txt", 2, "0")]
+    );
+    assert!(again.is_err());
+}
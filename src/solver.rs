@@ -0,0 +1,116 @@
+use std::any::Any;
+
+use anyhow::{anyhow, Result};
+
+use crate::{answer::Answer, day1, day2, day3, day4, day5, day6, day7, day8};
+
+/// One part of one day's puzzle.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+    fn part(&self) -> u8;
+    /// The puzzle title.
+    fn name(&self) -> &'static str;
+    fn solve(&self, input: &str) -> Result<Answer>;
+
+    /// Run only the parse phase. The result can be handed to
+    /// [`Solver::solve_parsed`].
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    /// Run only the solve phase on the output of [`Solver::parse`].
+    fn solve_parsed(&self, parsed: &dyn Any) -> Result<Answer>;
+}
+
+/// A puzzle part split into a parse phase and a solve phase, so the parsed
+/// input can be reused and each phase measured on its own.
+///
+/// Every `Puzzle` is also a [`Solver`].
+pub trait Puzzle {
+    const DAY: u8;
+    const PART: u8;
+    const NAME: &'static str;
+
+    type Input;
+    type Output: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn solve_parsed(input: &Self::Input) -> Result<Self::Output>;
+
+    /// Parse then solve.
+    fn solve(input: &str) -> Result<Self::Output> {
+        Self::solve_parsed(&Self::parse(input)?)
+    }
+}
+
+impl<P> Solver for P
+where
+    P: Puzzle + Sync,
+    P::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        P::DAY
+    }
+
+    fn part(&self) -> u8 {
+        P::PART
+    }
+
+    fn name(&self) -> &'static str {
+        P::NAME
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer> {
+        <P as Puzzle>::solve(input).map(Into::into)
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(<P as Puzzle>::parse(input)?))
+    }
+
+    fn solve_parsed(&self, parsed: &dyn Any) -> Result<Answer> {
+        let input = parsed
+            .downcast_ref()
+            .ok_or_else(|| anyhow!("input was not parsed by day{} part{}", P::DAY, P::PART))?;
+        <P as Puzzle>::solve_parsed(input).map(Into::into)
+    }
+}
+
+static SOLVERS: &[&dyn Solver] = &[
+    &day1::Part1,
+    &day1::Part2,
+    &day2::Part1,
+    &day2::Part2,
+    &day3::Part1,
+    &day3::Part2,
+    &day4::Part1,
+    &day4::Part2,
+    &day5::Part1,
+    &day5::Part2,
+    &day6::Part1,
+    &day6::Part2,
+    &day7::Part1,
+    &day7::Part2,
+    &day8::Part1,
+];
+
+/// Every implemented solver, ordered by day and part.
+pub fn solvers() -> &'static [&'static dyn Solver] {
+    SOLVERS
+}
+
+#[test]
+fn test_registry_order() {
+    let keys: Vec<_> = solvers().iter().map(|s| (s.day(), s.part())).collect();
+    let mut sorted = keys.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(keys, sorted);
+}
+
+#[test]
+fn test_parse_then_solve() {
+    let input = include_str!("../samples/day1.0.test.txt");
+    let (p1, p2) = (&day1::Part1 as &dyn Solver, &day1::Part2 as &dyn Solver);
+    let parsed = p1.parse(input).unwrap();
+    assert_eq!(p1.solve(input).unwrap(), p1.solve_parsed(&*parsed).unwrap());
+    assert_eq!(p2.solve(input).unwrap(), p2.solve_parsed(&*parsed).unwrap());
+    assert!(day4::Part1.solve_parsed(&*parsed).is_err());
+}
@@ -0,0 +1,110 @@
+//! Solves every sample in `samples/` and checks the answers recorded in its
+//! `.answers` sidecar, one test case per input and part.
+//!
+//! Adding an example is a matter of dropping `dayN.K.test.txt` and
+//! `dayN.K.test.answers` into `samples/`. Like libtest, any arguments that
+//! are not flags filter the cases by name.
+
+use std::process::ExitCode;
+
+use anyhow::{anyhow, bail, Result};
+use aoc2021::{assets, manifest::Manifest, solvers};
+
+struct Case {
+    name: String,
+    input: String,
+    day: u8,
+    part: u8,
+    expected: String,
+}
+
+fn cases() -> Result<Vec<Case>> {
+    let inputs = assets::discover(&assets::samples_dir())?;
+    let mut manifest = Manifest::default();
+    manifest.add_sidecars(&inputs)?;
+
+    let mut out = Vec::new();
+    for input in inputs.iter().filter(|i| i.example) {
+        let text = std::fs::read_to_string(&input.path)?;
+        let mut found = false;
+        for (_, part, expected) in manifest.iter().filter(|(name, ..)| *name == input.name()) {
+            found = true;
+            out.push(Case {
+                name: format!("{} part{}", input.name(), part),
+                input: text.clone(),
+                day: input.day,
+                part,
+                expected: expected.to_string(),
+            });
+        }
+        if !found {
+            bail!(
+                "{} has no answers in {}",
+                input.name(),
+                input.sidecar().display()
+            );
+        }
+    }
+    Ok(out)
+}
+
+fn check(case: &Case) -> Result<()> {
+    let solver = solvers()
+        .iter()
+        .find(|s| (s.day(), s.part()) == (case.day, case.part))
+        .ok_or_else(|| anyhow!("no solver for day{} part{}", case.day, case.part))?;
+    let answer = solver.solve(&case.input)?.to_string();
+    if answer != case.expected {
+        bail!("expected {}, got {}", case.expected, answer);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let filters: Vec<_> = std::env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with('-'))
+        .collect();
+    let cases = match cases() {
+        Ok(cases) => cases,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let cases: Vec<_> = cases
+        .into_iter()
+        .filter(|c| filters.is_empty() || filters.iter().any(|f| c.name.contains(f.as_str())))
+        .collect();
+
+    println!("\nrunning {} tests", cases.len());
+    let mut failures = Vec::new();
+    for case in &cases {
+        match check(case) {
+            Ok(()) => println!("test {} ... ok", case.name),
+            Err(e) => {
+                println!("test {} ... FAILED", case.name);
+                failures.push((&case.name, e));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, e) in &failures {
+            println!("    {}: {:#}", name, e);
+        }
+    }
+    let status = if failures.is_empty() { "ok" } else { "FAILED" };
+    println!(
+        "\ntest result: {}. {} passed; {} failed\n",
+        status,
+        cases.len() - failures.len(),
+        failures.len()
+    );
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}